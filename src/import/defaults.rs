@@ -58,6 +58,20 @@ lazy_static! {
             length: None,
         };
 
+    pub static ref MULTIDIM_ONE_HUNDRED: MultiDimensional =
+        MultiDimensional {
+            animated_property: schema::animated_properties::animated_property::AnimatedProperty {
+                property_index: None,
+                animated: Some(BoolInt::False),
+                expression: None,
+                slot_id: None,
+                value: schema::animated_properties::animated_property::AnimatedPropertyK::Static(
+                    vec![serde_json::Number::from(100), serde_json::Number::from(100), serde_json::Number::from(100)],
+                ),
+            },
+            length: None,
+        };
+
     pub static ref POSITION_ZERO: schema::animated_properties::position::Position = schema::animated_properties::position::Position {
         property_index: None,
         animated: Some(BoolInt::False),
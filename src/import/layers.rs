@@ -1,16 +1,18 @@
 use crate::parser;
-use crate::parser::schema::animated_properties::split_vector::SplitVector;
 use crate::parser::schema::helpers::int_boolean::BoolInt;
 use crate::runtime;
-use crate::runtime::model::animated::Position;
 use crate::runtime::model::{animated, Content, Layer, Value};
 use parser::schema;
 use vello::peniko::{self, BlendMode, Compose, Mix};
 
 use super::defaults::{
-    FLOAT_VALUE_ONE_HUNDRED, FLOAT_VALUE_ZERO, MULTIDIM_ONE, POSITION_ZERO,
+    FLOAT_VALUE_ONE_HUNDRED, FLOAT_VALUE_ZERO, MULTIDIM_ONE, MULTIDIM_ZERO,
+    POSITION_ZERO,
+};
+use super::properties::{
+    conv_hex_color, conv_pos_point, conv_rotation, conv_scalar,
+    conv_transform_position, conv_vec2,
 };
-use super::properties::{conv_pos_point, conv_scalar, conv_vec2};
 use super::{conv_blend_mode, conv_shape, conv_shape_geometry, NumberExt};
 
 pub fn conv_layer(
@@ -33,11 +35,7 @@ pub fn conv_layer(
 
             let params = setup_precomp_layer(precomp_layer, &mut layer);
             let name = precomp_layer.precomp_id.clone();
-            let time_remap_in = precomp_layer
-                .time_remap
-                .as_ref()
-                .unwrap_or(&FLOAT_VALUE_ZERO); // todo: verify that time remap should be 0 when none was parsed
-            let time_remap = conv_scalar(time_remap_in);
+            let time_remap = precomp_layer.time_remap.as_ref().map(conv_scalar);
             layer.content = Content::Instance { name, time_remap };
 
             params
@@ -63,7 +61,16 @@ pub fn conv_layer(
                 return None;
             }
 
-            setup_layer_base(&solid_color_layer.properties, &mut layer)
+            let params =
+                setup_layer_base(&solid_color_layer.properties, &mut layer);
+            layer.width = solid_color_layer.width.unwrap_u32();
+            layer.height = solid_color_layer.height.unwrap_u32();
+            // Colors that fail to parse are drawn black.
+            let color = conv_hex_color(&solid_color_layer.color)
+                .unwrap_or(peniko::Color::BLACK);
+            layer.content = Content::Solid(color);
+
+            params
         }
     };
 
@@ -84,6 +91,9 @@ fn setup_precomp_layer(
     let (transform, opacity) = conv_transform(&source.properties.transform);
     target.transform = transform;
     target.opacity = opacity;
+    target.auto_orient = conv_auto_orient(
+        source.properties.rotate_to_match_anim_pos_path.as_ref(),
+    );
     target.width = source.width.unwrap_u32();
     target.height = source.height.unwrap_u32();
     target.is_mask = source
@@ -124,37 +134,17 @@ fn setup_precomp_layer(
         .properties
         .time_stretch
         .as_ref()
-        .map_or(0.0, |sr| sr.unwrap_f32());
+        .map_or(1.0, |sr| sr.unwrap_f32());
     target.start_frame = source.properties.start_time.unwrap_f32();
-    target.stretch = source
-        .properties
-        .time_stretch
-        .as_ref()
-        .map_or(0.0, |sr| sr.unwrap_f32());
 
-    for mask_source in source
-        .properties
-        .masks_properties
-        .as_ref()
-        .unwrap_or(&Vec::default())
-    {
-        if let Some(shape) = &mask_source.shape {
-            if let Some(geometry) = conv_shape_geometry(shape) {
-                let mode = peniko::BlendMode::default();
-                let opacity = conv_scalar(
-                    mask_source
-                        .opacity
-                        .as_ref()
-                        .unwrap_or(&FLOAT_VALUE_ONE_HUNDRED),
-                );
-                target.masks.push(runtime::model::Mask {
-                    mode,
-                    geometry,
-                    opacity,
-                })
-            }
-        }
-    }
+    target.masks.extend(
+        source
+            .properties
+            .masks_properties
+            .iter()
+            .flatten()
+            .filter_map(conv_mask),
+    );
 
     (
         source
@@ -179,6 +169,9 @@ fn setup_shape_layer(
     let (transform, opacity) = conv_transform(&source.properties.transform);
     target.transform = transform;
     target.opacity = opacity;
+    target.auto_orient = conv_auto_orient(
+        source.properties.rotate_to_match_anim_pos_path.as_ref(),
+    );
     target.is_mask = source
         .properties
         .matte_target
@@ -217,37 +210,17 @@ fn setup_shape_layer(
         .properties
         .time_stretch
         .as_ref()
-        .map_or(0.0, |sr| sr.unwrap_f32());
+        .map_or(1.0, |sr| sr.unwrap_f32());
     target.start_frame = source.properties.start_time.unwrap_f32();
-    target.stretch = source
-        .properties
-        .time_stretch
-        .as_ref()
-        .map_or(0.0, |sr| sr.unwrap_f32());
 
-    for mask_source in source
-        .properties
-        .masks_properties
-        .as_ref()
-        .unwrap_or(&Vec::default())
-    {
-        if let Some(shape) = &mask_source.shape {
-            if let Some(geometry) = conv_shape_geometry(shape) {
-                let mode = peniko::BlendMode::default();
-                let opacity = conv_scalar(
-                    mask_source
-                        .opacity
-                        .as_ref()
-                        .unwrap_or(&FLOAT_VALUE_ONE_HUNDRED),
-                );
-                target.masks.push(runtime::model::Mask {
-                    mode,
-                    geometry,
-                    opacity,
-                })
-            }
-        }
-    }
+    target.masks.extend(
+        source
+            .properties
+            .masks_properties
+            .iter()
+            .flatten()
+            .filter_map(conv_mask),
+    );
 
     (
         source
@@ -271,6 +244,8 @@ fn setup_layer_base(
     let (transform, opacity) = conv_transform(&source.transform);
     target.transform = transform;
     target.opacity = opacity;
+    target.auto_orient =
+        conv_auto_orient(source.rotate_to_match_anim_pos_path.as_ref());
     target.is_mask = source
         .matte_target
         .as_ref()
@@ -298,33 +273,16 @@ fn setup_layer_base(
     target.stretch = source
         .time_stretch
         .as_ref()
-        .map_or(0.0, |sr| sr.unwrap_f32());
+        .map_or(1.0, |sr| sr.unwrap_f32());
     target.start_frame = source.start_time.unwrap_f32();
-    target.stretch = source
-        .time_stretch
-        .as_ref()
-        .map_or(0.0, |sr| sr.unwrap_f32());
 
-    for mask_source in
-        source.masks_properties.as_ref().unwrap_or(&Vec::default())
-    {
-        if let Some(shape) = &mask_source.shape {
-            if let Some(geometry) = conv_shape_geometry(shape) {
-                let mode = peniko::BlendMode::default();
-                let opacity = conv_scalar(
-                    mask_source
-                        .opacity
-                        .as_ref()
-                        .unwrap_or(&FLOAT_VALUE_ONE_HUNDRED),
-                );
-                target.masks.push(runtime::model::Mask {
-                    mode,
-                    geometry,
-                    opacity,
-                })
-            }
-        }
-    }
+    target.masks.extend(
+        source
+            .masks_properties
+            .iter()
+            .flatten()
+            .filter_map(conv_mask),
+    );
 
     (
         source.index.as_ref().map_or(0, |ind| ind.unwrap_u32()) as usize,
@@ -332,34 +290,50 @@ fn setup_layer_base(
     )
 }
 
-fn conv_transform(
-    value: &parser::schema::helpers::transform::Transform,
-) -> (runtime::model::Transform, Value<f32>) {
-    let rotation_in = match &value.rotation {
-        Some(any_trans) => match any_trans {
-            parser::schema::helpers::transform::AnyTransformR::Rotation(float_value) => float_value,
-            // todo: need to actually handle split rotations
-            parser::schema::helpers::transform::AnyTransformR::SplitRotation { .. } => todo!(),
-        },
-        None => todo!("split rotation"),
+fn conv_mask(
+    source: &schema::helpers::mask::Mask,
+) -> Option<runtime::model::Mask> {
+    use runtime::model::MaskMode;
+    use schema::constants::mask_mode::MaskMode as SourceMode;
+    let mode = match source.mode.clone().unwrap_or_default() {
+        SourceMode::None => return None,
+        SourceMode::Add => MaskMode::Add,
+        SourceMode::Subtract => MaskMode::Subtract,
+        SourceMode::Intersect => MaskMode::Intersect,
+        SourceMode::Lighten => MaskMode::Lighten,
+        SourceMode::Darken => MaskMode::Darken,
+        SourceMode::Difference => MaskMode::Difference,
     };
+    let geometry = conv_shape_geometry(source.shape.as_ref()?)?;
+    let opacity = conv_scalar(
+        source.opacity.as_ref().unwrap_or(&FLOAT_VALUE_ONE_HUNDRED),
+    );
+    let expansion =
+        conv_scalar(source.expand.as_ref().unwrap_or(&FLOAT_VALUE_ZERO));
+    Some(runtime::model::Mask {
+        mode,
+        geometry,
+        opacity,
+        is_inverted: source.inverted.unwrap_or(false),
+        expansion,
+        feather: conv_vec2(source.feather.as_ref().unwrap_or(&MULTIDIM_ZERO)),
+    })
+}
 
-    let position = match &value.position {
-        schema::helpers::transform::AnyTransformP::Position(position) => {
-            Position::Value(conv_pos_point(position))
-        }
-        schema::helpers::transform::AnyTransformP::SplitPosition(
-            SplitVector { x, y, .. },
-        ) => Position::SplitValues((conv_scalar(x), conv_scalar(y))),
-    };
+fn conv_auto_orient(source: Option<&BoolInt>) -> bool {
+    source.map_or(false, |ao| *ao == BoolInt::True)
+}
 
+fn conv_transform(
+    value: &parser::schema::helpers::transform::Transform,
+) -> (runtime::model::Transform, Value<f32>) {
     let transform = animated::Transform {
         anchor: conv_pos_point(
             value.anchor_point.as_ref().unwrap_or(&POSITION_ZERO),
         ),
-        position,
+        position: conv_transform_position(&value.position),
         scale: conv_vec2(value.scale.as_ref().unwrap_or(&MULTIDIM_ONE)),
-        rotation: conv_scalar(rotation_in),
+        rotation: conv_rotation(value.rotation.as_ref()),
         skew: conv_scalar(value.skew.as_ref().unwrap_or(&FLOAT_VALUE_ZERO)),
         skew_angle: conv_scalar(
             value.skew_axis.as_ref().unwrap_or(&FLOAT_VALUE_ZERO),
@@ -1,24 +1,27 @@
-use crate::import::properties::{conv_color, conv_multi_point, conv_size};
+use crate::import::properties::{
+    conv_color, conv_multi_point, conv_size, conv_time,
+};
 use crate::parser::schema::animated_properties::gradient_colors::GradientColors;
 use crate::parser::schema::constants::gradient_type::GradientType;
-use crate::parser::schema::helpers::int_boolean::BoolInt;
 use crate::parser::{self, Lottie};
-use crate::runtime::model::animated::Position;
 use crate::runtime::model::{
-    animated, Draw, GroupTransform, Lerp, SplineToPath, Time, Value,
+    animated, Draw, GroupTransform, SplineToPath, Value,
 };
 use crate::runtime::{self, Composition};
 use parser::schema;
-use serde_json::Number;
 use std::collections::HashMap;
 use vello::kurbo::{Cap, Join, Point};
-use vello::peniko::{BlendMode, Mix};
+use vello::peniko::{BlendMode, Compose, Fill, Mix};
 
 use self::defaults::{
-    FLOAT_VALUE_ONE_HUNDRED, FLOAT_VALUE_ZERO, MULTIDIM_ONE, POSITION_ZERO,
+    FLOAT_VALUE_ONE_HUNDRED, FLOAT_VALUE_ZERO, MULTIDIM_ONE,
+    MULTIDIM_ONE_HUNDRED, POSITION_ZERO,
 };
 use self::layers::conv_layer;
-use self::properties::{conv_pos_point, conv_scalar, conv_vec2};
+use self::properties::{
+    conv_pos_point, conv_rotation, conv_scalar, conv_transform_position,
+    conv_vec2,
+};
 
 mod defaults;
 mod layers;
@@ -133,24 +136,6 @@ pub fn import_composition(
 fn conv_shape_transform(
     value: &parser::schema::shapes::transform::TransformShape,
 ) -> GroupTransform {
-    let rotation_in = match &value.transform.rotation {
-        Some(any_trans) => match any_trans {
-            parser::schema::helpers::transform::AnyTransformR::Rotation(float_value) => float_value,
-            // todo: need to actually handle split rotations
-            parser::schema::helpers::transform::AnyTransformR::SplitRotation { .. } => todo!("split rotation"),
-        },
-        None => &FLOAT_VALUE_ZERO,
-    };
-    let position_in = match &value.transform.position {
-        schema::helpers::transform::AnyTransformP::Position(position) => {
-            position
-        }
-        schema::helpers::transform::AnyTransformP::SplitPosition(_) => {
-            // todo: split vectors
-            todo!("split position");
-        }
-    };
-
     let transform = animated::Transform {
         anchor: conv_pos_point(
             value
@@ -159,11 +144,11 @@ fn conv_shape_transform(
                 .as_ref()
                 .unwrap_or(&POSITION_ZERO),
         ),
-        position: Position::Value(conv_pos_point(position_in)),
+        position: conv_transform_position(&value.transform.position),
         scale: conv_vec2(
             value.transform.scale.as_ref().unwrap_or(&MULTIDIM_ONE),
         ),
-        rotation: conv_scalar(rotation_in),
+        rotation: conv_rotation(value.transform.rotation.as_ref()),
         skew: conv_scalar(
             value.transform.skew.as_ref().unwrap_or(&FLOAT_VALUE_ZERO),
         ),
@@ -187,119 +172,29 @@ fn conv_shape_transform(
     GroupTransform {
         transform: transform.to_model(),
         opacity,
+        blend_mode: None,
     }
 }
 
-fn normalize_to_range(a: f32, b: f32, x: f32) -> f32 {
-    if a == b {
-        // Avoid division by zero if a and b are the same
-        return 0.0;
-    }
-
-    // Calculate the normalized value
-    (x - a) / (b - a)
-}
-
-fn calc_stops(value: &[Number], count: usize) -> Vec<[f64; 5]> {
-    let mut stops: Vec<[f64; 5]> = Vec::new();
-    let mut alpha_stops: Vec<(f32, f64)> = Vec::new();
-    for chunk in value.chunks_exact(4) {
-        stops.push([
-            chunk[0].unwrap_f64(),
-            chunk[1].unwrap_f64(),
-            chunk[2].unwrap_f64(),
-            chunk[3].unwrap_f64(),
-            1.0,
-        ]);
-        if stops.len() >= count {
-            // there is alpha data at the end of the list, which is a sequence of (offset, alpha) pairs
-            for chunk in value.chunks_exact(2).skip(count * 2) {
-                let offset = chunk[0].unwrap_f32();
-                let alpha = chunk[1].unwrap_f64();
-                alpha_stops.push((offset, alpha));
-            }
-
-            for stop in stops.iter_mut() {
-                let mut last: Option<(f32, f64)> = None;
-                for &(b, alpha_b) in alpha_stops.iter() {
-                    if let Some((a, alpha_a)) = last.take() {
-                        let x = stop[0] as f32;
-                        let t = normalize_to_range(a, b, x);
-
-                        let alpha_interp = alpha_a.lerp(&alpha_b, t);
-                        let alpha_interp = if (x >= a && x <= b)
-                            && (t <= 0.25)
-                            && (x <= 0.1)
-                        {
-                            alpha_a
-                        } else {
-                            alpha_interp
-                        }; // todo: this is a hack to get alpha rendering with a falloff similar to lottiefiles'
-
-                        let alpha_interp = if (x >= a && x <= b)
-                            && (t >= 0.75)
-                            && (x >= 0.9)
-                        {
-                            alpha_b
-                        } else {
-                            alpha_interp
-                        }; // todo: this is a hack to get alpha rendering with a falloff similar to lottiefiles'
-
-                        stop[4] = stop[4].min(alpha_interp);
-                    }
-                    last = Some((b, alpha_b));
-                }
-            }
-            break;
-        }
-    }
-
-    stops
-}
-
 fn conv_gradient_colors(value: &GradientColors) -> runtime::model::ColorStops {
     use schema::animated_properties::animated_property::AnimatedPropertyK::*;
 
     let count = value.count.unwrap_u32() as usize;
     match &value.colors.animated_property.value {
-        Static(value) => runtime::model::ColorStops::Fixed({
-            let mut stops = runtime::model::fixed::ColorStops::new();
-            let raw = calc_stops(value, count);
-            for values in raw {
-                stops.push(
-                    (
-                        values[0] as f32,
-                        runtime::model::fixed::Color::rgba(
-                            values[1], values[2], values[3], values[4],
-                        ),
-                    )
-                        .into(),
-                );
-            }
-            stops
-        }),
+        Static(value) => {
+            let values =
+                value.iter().map(|x| x.unwrap_f32()).collect::<Vec<_>>();
+            runtime::model::ColorStops::Fixed(
+                runtime::model::fixed::color_stops(&values, count),
+            )
+        }
         AnimatedValue(animated) => {
             let mut frames = vec![];
             let mut values: Vec<Vec<f32>> = vec![];
             for value in animated {
-                let hold = value
-                    .base
-                    .hold
-                    .as_ref()
-                    .map(|b| b.eq(&BoolInt::True))
-                    .unwrap_or(false);
-                frames.push(Time {
-                    frame: value.base.time.unwrap_f32(),
-                    hold,
-                });
-
-                let stops = calc_stops(&value.value, count)
-                    .into_iter()
-                    .flatten()
-                    .map(|x| x as f32)
-                    .collect::<Vec<_>>();
-
-                values.push(stops);
+                frames.push(conv_time(&value.base));
+                values
+                    .push(value.value.iter().map(|x| x.unwrap_f32()).collect());
             }
             runtime::model::ColorStops::Animated(animated::ColorStops {
                 frames,
@@ -324,11 +219,15 @@ fn conv_draw(value: &schema::shapes::AnyShape) -> Option<runtime::model::Draw> {
             );
             Some(runtime::model::Draw {
                 stroke: None,
+                fill_rule: conv_fill_rule(value.fill_rule.as_ref()),
                 brush,
                 opacity,
+                blend_mode: conv_shape_blend_mode(&value.shape_element),
             })
         }
         AnyShape::Stroke(value) => {
+            let (dash_pattern, dash_offset) =
+                conv_stroke_dashes(value.dash_array.as_deref());
             let stroke = animated::Stroke {
                 width: conv_scalar(&value.stroke_width),
                 join: match value.line_join.as_ref().unwrap_or(&LineJoin::Bevel)
@@ -346,14 +245,18 @@ fn conv_draw(value: &schema::shapes::AnyShape) -> Option<runtime::model::Draw> {
                     LineCap::Round => Cap::Round,
                     LineCap::Square => Cap::Square,
                 },
+                dash_pattern,
+                dash_offset,
             };
             let color = conv_color(&value.stroke_color);
             let brush = animated::Brush::Solid(color).to_model();
             let opacity = conv_scalar(&value.opacity);
             Some(runtime::model::Draw {
                 stroke: Some(stroke.to_model()),
+                fill_rule: Fill::NonZero,
                 brush,
                 opacity,
+                blend_mode: conv_shape_blend_mode(&value.shape_element),
             })
         }
         AnyShape::GradientFill(value) => {
@@ -367,16 +270,30 @@ fn conv_draw(value: &schema::shapes::AnyShape) -> Option<runtime::model::Draw> {
                 is_radial,
                 start_point,
                 end_point,
+                highlight_length: value
+                    .gradient
+                    .highlight_length
+                    .as_ref()
+                    .map_or(Value::Fixed(0.0), conv_scalar),
+                highlight_angle: value
+                    .gradient
+                    .highlight_angle
+                    .as_ref()
+                    .map_or(Value::Fixed(0.0), conv_scalar),
                 stops: conv_gradient_colors(&value.gradient.colors),
             };
             let brush = animated::Brush::Gradient(gradient).to_model();
             Some(Draw {
                 stroke: None,
+                fill_rule: conv_fill_rule(value.fill_rule.as_ref()),
                 brush,
-                opacity: Value::Fixed(100.0),
+                opacity: conv_scalar(&value.opacity),
+                blend_mode: conv_shape_blend_mode(&value.shape_element),
             })
         }
         AnyShape::GradientStroke(value) => {
+            let (dash_pattern, dash_offset) =
+                conv_stroke_dashes(value.base_stroke.dashes.as_deref());
             let stroke = animated::Stroke {
                 width: conv_scalar(&value.base_stroke.width),
                 join: match value
@@ -404,6 +321,8 @@ fn conv_draw(value: &schema::shapes::AnyShape) -> Option<runtime::model::Draw> {
                     LineCap::Round => Cap::Round,
                     LineCap::Square => Cap::Square,
                 },
+                dash_pattern,
+                dash_offset,
             };
             let is_radial = matches!(
                 value
@@ -419,13 +338,25 @@ fn conv_draw(value: &schema::shapes::AnyShape) -> Option<runtime::model::Draw> {
                 is_radial,
                 start_point,
                 end_point,
+                highlight_length: value
+                    .gradient
+                    .highlight_length
+                    .as_ref()
+                    .map_or(Value::Fixed(0.0), conv_scalar),
+                highlight_angle: value
+                    .gradient
+                    .highlight_angle
+                    .as_ref()
+                    .map_or(Value::Fixed(0.0), conv_scalar),
                 stops: conv_gradient_colors(&value.gradient.colors),
             };
             let brush = animated::Brush::Gradient(gradient).to_model();
             Some(Draw {
                 stroke: Some(stroke.to_model()),
+                fill_rule: Fill::NonZero,
                 brush,
-                opacity: Value::Fixed(100.0),
+                opacity: conv_scalar(&value.base_stroke.opacity),
+                blend_mode: conv_shape_blend_mode(&value.shape_element),
             })
         }
         _ => None,
@@ -457,6 +388,17 @@ fn conv_shape(
                     }
                 }
             }
+            if let Some(blend_mode) =
+                conv_shape_blend_mode(&value.shape_element)
+            {
+                group_transform
+                    .get_or_insert_with(|| GroupTransform {
+                        transform: Default::default(),
+                        opacity: Value::Fixed(100.0),
+                        blend_mode: None,
+                    })
+                    .blend_mode = Some(blend_mode);
+            }
             if !shapes.is_empty() {
                 Some(crate::runtime::model::Shape::Group(
                     shapes,
@@ -466,20 +408,120 @@ fn conv_shape(
                 None
             }
         }
-        // todo: implement repeater shape
-        // shapes::Shape::Repeater(value) => {
-        //     let repeater = animated::Repeater {
-        //         copies: conv_scalar(&value.copies),
-        //         offset: conv_scalar(&value.offset),
-        //         anchor_point: conv_point(&value.transform.anchor_point),
-        //         position: conv_point(&value.transform.position),
-        //         rotation: conv_scalar(&value.transform.rotation),
-        //         scale: conv_vec2(&value.transform.scale),
-        //         start_opacity: conv_scalar(&value.transform.start_opacity),
-        //         end_opacity: conv_scalar(&value.transform.end_opacity),
-        //     };
-        //     Some(Shape::Repeater(repeater.to_model()))
-        // }
+        schema::shapes::AnyShape::Trim(value) => {
+            use schema::constants::trim_multiple_shapes::TrimMultipleShapes;
+            let trim = animated::Trim {
+                start: conv_scalar(&value.start),
+                end: conv_scalar(&value.end),
+                offset: conv_scalar(&value.offset),
+                is_individual: value.multiple
+                    == Some(TrimMultipleShapes::Individually),
+            };
+            Some(crate::runtime::model::Shape::Trim(trim.to_model()))
+        }
+        schema::shapes::AnyShape::Merge(value) => {
+            use crate::runtime::model::MergeMode;
+            use schema::constants::merge_mode::MergeMode::*;
+            let mode = match value.merge_mode.as_ref().unwrap_or(&Normal) {
+                Normal => MergeMode::Merge,
+                Add => MergeMode::Add,
+                Subtract => MergeMode::Subtract,
+                Intersect => MergeMode::Intersect,
+                ExcludeIntersections => MergeMode::ExcludeIntersections,
+            };
+            Some(crate::runtime::model::Shape::Merge(mode))
+        }
+        schema::shapes::AnyShape::OffsetPath(value) => {
+            use schema::constants::line_join::LineJoin;
+            let offset = animated::OffsetPath {
+                amount: conv_scalar(
+                    value.amount.as_ref().unwrap_or(&FLOAT_VALUE_ZERO),
+                ),
+                join: match value.line_join.as_ref().unwrap_or(&LineJoin::Round)
+                {
+                    LineJoin::Bevel => Join::Bevel,
+                    LineJoin::Round => Join::Round,
+                    LineJoin::Miter => Join::Miter,
+                },
+                miter_limit: value
+                    .miter_limit
+                    .as_ref()
+                    .map_or(Value::Fixed(4.0), conv_scalar),
+            };
+            Some(crate::runtime::model::Shape::OffsetPath(offset.to_model()))
+        }
+        schema::shapes::AnyShape::PuckerBloat(value) => {
+            Some(crate::runtime::model::Shape::PuckerBloat(conv_scalar(
+                value.amount.as_ref().unwrap_or(&FLOAT_VALUE_ZERO),
+            )))
+        }
+        schema::shapes::AnyShape::ZigZag(value) => {
+            let zig_zag = animated::ZigZag {
+                frequency: conv_scalar(&value.frequency),
+                amplitude: conv_scalar(&value.amplitude),
+                point_type: conv_scalar(&value.point_type),
+            };
+            Some(crate::runtime::model::Shape::ZigZag(zig_zag.to_model()))
+        }
+        schema::shapes::AnyShape::Twist(value) => {
+            let twist = animated::Twist {
+                angle: conv_scalar(&value.angle),
+                center: conv_multi_point(&value.center),
+            };
+            Some(crate::runtime::model::Shape::Twist(twist.to_model()))
+        }
+        schema::shapes::AnyShape::RoundedCorners(value) => {
+            Some(crate::runtime::model::Shape::RoundedCorners(conv_scalar(
+                &value.radius,
+            )))
+        }
+        schema::shapes::AnyShape::Repeater(value) => {
+            use schema::constants::composite::Composite;
+            use schema::helpers::transform::AnyTransformR;
+            let transform = &value.transform.transform;
+            let repeater = animated::Repeater {
+                copies: conv_scalar(&value.copies),
+                offset: conv_scalar(
+                    value.offset.as_ref().unwrap_or(&FLOAT_VALUE_ZERO),
+                ),
+                anchor_point: conv_pos_point(
+                    transform.anchor_point.as_ref().unwrap_or(&POSITION_ZERO),
+                ),
+                position: conv_transform_position(&transform.position),
+                rotation: match &transform.rotation {
+                    Some(AnyTransformR::Rotation(rotation)) => {
+                        conv_scalar(rotation)
+                    }
+                    // Only the rotation around the z axis affects the
+                    // copies.
+                    Some(AnyTransformR::SplitRotation {
+                        z_rotation, ..
+                    }) => conv_scalar(
+                        z_rotation.as_ref().unwrap_or(&FLOAT_VALUE_ZERO),
+                    ),
+                    None => Value::Fixed(0.0),
+                },
+                scale: conv_vec2(
+                    transform.scale.as_ref().unwrap_or(&MULTIDIM_ONE_HUNDRED),
+                ),
+                start_opacity: conv_scalar(
+                    value
+                        .transform
+                        .start_opacity
+                        .as_ref()
+                        .unwrap_or(&FLOAT_VALUE_ONE_HUNDRED),
+                ),
+                end_opacity: conv_scalar(
+                    value
+                        .transform
+                        .end_opacity
+                        .as_ref()
+                        .unwrap_or(&FLOAT_VALUE_ONE_HUNDRED),
+                ),
+                is_above: value.composite != Some(Composite::Below),
+            };
+            Some(crate::runtime::model::Shape::Repeater(repeater.to_model()))
+        }
         _ => None,
     }
 }
@@ -491,7 +533,7 @@ fn conv_geometry(
     match value {
         AnyShape::Ellipse(value) => {
             let ellipse = animated::Ellipse {
-                is_ccw: false, // todo: lottie schema does not have a field for this (anymore?)
+                is_ccw: conv_is_ccw(value.direction.as_ref()),
                 position: conv_pos_point(&value.position),
                 size: conv_size(&value.size),
             };
@@ -499,13 +541,32 @@ fn conv_geometry(
         }
         AnyShape::Rectangle(value) => {
             let rect = animated::Rect {
-                is_ccw: false, // todo: lottie schema does not have a field for this (anymore?)
+                is_ccw: conv_is_ccw(value.direction.as_ref()),
                 position: conv_pos_point(&value.position),
                 size: conv_size(&value.size),
                 corner_radius: conv_scalar(&value.rounded_corner_radius),
             };
             Some(crate::runtime::model::Geometry::Rect(rect))
         }
+        AnyShape::PolyStar(value) => {
+            use schema::constants::star_type::StarType;
+            let star = animated::Star {
+                is_polygon: value.star_type == StarType::Polygon,
+                is_ccw: conv_is_ccw(value.direction.as_ref()),
+                position: conv_pos_point(&value.position),
+                inner_radius: conv_scalar(
+                    value.inner_radius.as_ref().unwrap_or(&FLOAT_VALUE_ZERO),
+                ),
+                inner_roundness: conv_scalar(
+                    value.inner_roundness.as_ref().unwrap_or(&FLOAT_VALUE_ZERO),
+                ),
+                outer_radius: conv_scalar(&value.outer_radius),
+                outer_roundness: conv_scalar(&value.outer_roundness),
+                rotation: conv_scalar(&value.rotation),
+                points: conv_scalar(&value.points),
+            };
+            Some(crate::runtime::model::Geometry::Star(star))
+        }
         AnyShape::Path(value) => conv_shape_geometry(&value.shape_property),
         // todo: generic shape
         _ => None,
@@ -528,16 +589,7 @@ fn conv_shape_geometry(
             let mut frames = vec![];
             let mut values = vec![];
             for value in animated {
-                let hold = value
-                    .base
-                    .hold
-                    .as_ref()
-                    .map(|b| b.eq(&BoolInt::True))
-                    .unwrap_or(false);
-                frames.push(Time {
-                    frame: value.base.time.unwrap_f32(),
-                    hold,
-                });
+                frames.push(conv_time(&value.base));
                 let (points, is_frame_closed) =
                     conv_spline(value.start.get(0)?);
                 values.push(points);
@@ -576,6 +628,48 @@ fn conv_spline(value: &schema::helpers::bezier::Bezier) -> (Vec<Point>, bool) {
     (points, is_closed)
 }
 
+fn conv_stroke_dashes(
+    value: Option<&[schema::shapes::stroke_dash::StrokeDash]>,
+) -> (Vec<Value<f32>>, Value<f32>) {
+    use schema::constants::stroke_dash_type::StrokeDashType;
+
+    let mut pattern = vec![];
+    let mut offset = Value::Fixed(0.0);
+    for dash in value.unwrap_or_default() {
+        let Some(length) = dash.length.as_ref().map(conv_scalar) else {
+            continue;
+        };
+        match dash.dash_type.as_ref().unwrap_or(&StrokeDashType::Dash) {
+            StrokeDashType::Dash | StrokeDashType::Gap => pattern.push(length),
+            StrokeDashType::Offset => offset = length,
+        }
+    }
+    (pattern, offset)
+}
+
+fn conv_fill_rule(
+    value: Option<&schema::constants::fill_rule::FillRule>,
+) -> Fill {
+    use schema::constants::fill_rule::FillRule;
+    match value {
+        Some(FillRule::EvenOdd) => Fill::EvenOdd,
+        Some(FillRule::NonZero) | None => Fill::NonZero,
+    }
+}
+
+fn conv_is_ccw(
+    value: Option<&schema::constants::shape_direction::ShapeDirection>,
+) -> bool {
+    use schema::constants::shape_direction::ShapeDirection;
+    value == Some(&ShapeDirection::Reversed)
+}
+
+fn conv_shape_blend_mode(
+    value: &schema::shapes::shape_element::ShapeElement,
+) -> Option<BlendMode> {
+    value.blend_mode.as_ref().and_then(conv_blend_mode)
+}
+
 fn conv_blend_mode(
     value: &crate::parser::schema::constants::blend_mode::BlendMode,
 ) -> Option<BlendMode> {
@@ -598,7 +692,8 @@ fn conv_blend_mode(
         Saturation => BlendMode::from(Mix::Saturation),
         Color => BlendMode::from(Mix::Color),
         Luminosity => BlendMode::from(Mix::Luminosity),
-        Add => unimplemented!(),
-        HardMix => unimplemented!(),
+        Add => BlendMode::new(Mix::Normal, Compose::Plus),
+        // There is no hard mix in vello, hard light is the closest match.
+        HardMix => BlendMode::from(Mix::HardLight),
     })
 }
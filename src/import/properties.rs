@@ -1,12 +1,89 @@
+use crate::import::defaults::{FLOAT_VALUE_ZERO, MULTIDIM_ZERO};
 use crate::import::NumberExt;
 use crate::parser;
+use crate::parser::schema::animated_properties::keyframe_base::KeyframeBase;
+use crate::parser::schema::animated_properties::keyframe_bezier_handle::{
+    KeyframeBezierHandle, KeyframeComponent,
+};
 use crate::parser::schema::animated_properties::multi_dimensional::MultiDimensional;
+use crate::parser::schema::animated_properties::split_vector::SplitVector;
 use crate::parser::schema::helpers::int_boolean::BoolInt;
-use crate::runtime::model::{self, Lerp, Time, Value};
+use crate::parser::schema::helpers::transform::{AnyTransformP, AnyTransformR};
+use crate::runtime::model::animated::{self, Position, Rotation};
+use crate::runtime::model::{self, Easing, Lerp, Time, Value};
 use parser::schema;
 use vello::kurbo::{Point, Size, Vec2};
 use vello::peniko::Color;
 
+/// Converts the time, hold flag and easing curve of a keyframe.
+pub fn conv_time(base: &KeyframeBase) -> Time {
+    let hold = base
+        .hold
+        .as_ref()
+        .map(|b| b.eq(&BoolInt::True))
+        .unwrap_or(false);
+    Time {
+        frame: base.time.unwrap_f32(),
+        hold,
+        easing: conv_easing(base, 0),
+        component_easing: conv_component_easing(base),
+    }
+}
+
+/// Converts the bezier handles of a keyframe into a timing function for the
+/// given dimension. Returns `None` (linear) if either handle is missing.
+fn conv_easing(base: &KeyframeBase, dimension: usize) -> Option<Easing> {
+    let out_tangent =
+        conv_easing_handle(base.out_tangent.as_ref()?, dimension)?;
+    let in_tangent = conv_easing_handle(base.in_tangent.as_ref()?, dimension)?;
+    Some(Easing {
+        out_tangent,
+        in_tangent,
+    })
+}
+
+/// Converts per-dimension bezier handles into separate timing functions for
+/// each component. Returns `None` if the handles apply to all dimensions.
+fn conv_component_easing(base: &KeyframeBase) -> Option<[Easing; 3]> {
+    let handles = [base.out_tangent.as_ref()?, base.in_tangent.as_ref()?];
+    let is_split = handles.iter().any(|handle| {
+        component_len(&handle.x_coordinate) > 1
+            || component_len(&handle.y_coordinate) > 1
+    });
+    if !is_split {
+        return None;
+    }
+    Some([
+        conv_easing(base, 0)?,
+        conv_easing(base, 1)?,
+        conv_easing(base, 2)?,
+    ])
+}
+
+fn component_len(component: &KeyframeComponent) -> usize {
+    match component {
+        KeyframeComponent::SingleValue(_) => 1,
+        KeyframeComponent::ArrayOfValues(numbers) => numbers.len(),
+    }
+}
+
+fn conv_easing_handle(
+    handle: &KeyframeBezierHandle,
+    dimension: usize,
+) -> Option<Point> {
+    // Handles with fewer entries than dimensions reuse the last entry.
+    let get = |component: &KeyframeComponent| match component {
+        KeyframeComponent::SingleValue(number) => number.as_f64(),
+        KeyframeComponent::ArrayOfValues(numbers) => {
+            numbers.get(dimension).or_else(|| numbers.last())?.as_f64()
+        }
+    };
+    Some(Point::new(
+        get(&handle.x_coordinate)?,
+        get(&handle.y_coordinate)?,
+    ))
+}
+
 pub fn conv_scalar(
     float_value: &parser::schema::animated_properties::value::FloatValue,
 ) -> Value<f32> {
@@ -17,18 +94,8 @@ pub fn conv_scalar(
             let mut frames = vec![];
             let mut values = vec![];
             for keyframe in keyframes {
-                let start_time = keyframe.base.time.unwrap_f32();
                 let data = keyframe.value[0].unwrap_f32();
-                let hold = keyframe
-                    .base
-                    .hold
-                    .as_ref()
-                    .map(|b| b.eq(&BoolInt::True))
-                    .unwrap_or(false);
-                frames.push(crate::runtime::model::Time {
-                    frame: start_time,
-                    hold,
-                });
+                frames.push(conv_time(&keyframe.base));
                 values.push(data);
                 // todo: end_value deprecated but should we still push it if it exists?
             }
@@ -63,16 +130,7 @@ pub fn conv_multi<T: Lerp>(
                     .iter()
                     .map(|number| number.as_f64().unwrap())
                     .collect();
-                let hold = keyframe
-                    .base
-                    .hold
-                    .as_ref()
-                    .map(|b| b.eq(&BoolInt::True))
-                    .unwrap_or(false);
-                frames.push(Time {
-                    frame: keyframe.base.time.unwrap_f32(),
-                    hold,
-                });
+                frames.push(conv_time(&keyframe.base));
                 values.push(f(&data));
                 // todo: end_value deprecated but should we still append it if it exists?
             }
@@ -107,16 +165,7 @@ pub fn conv_multi_color<T: Lerp>(
                     .iter()
                     .map(|number| number.as_f64().unwrap())
                     .collect();
-                let hold = keyframe
-                    .base
-                    .hold
-                    .as_ref()
-                    .map(|b| b.eq(&BoolInt::True))
-                    .unwrap_or(false);
-                frames.push(Time {
-                    frame: keyframe.base.time.unwrap_f32(),
-                    hold,
-                });
+                frames.push(conv_time(&keyframe.base));
                 values.push(f(&data));
                 // todo: end_value deprecated but should we still append it if it exists?
             }
@@ -152,17 +201,7 @@ pub fn conv_pos<T: Lerp>(
                     .iter()
                     .map(|number| number.as_f64().unwrap())
                     .collect();
-                let hold = keyframe
-                    .keyframe
-                    .base
-                    .hold
-                    .as_ref()
-                    .map(|b| b.eq(&BoolInt::True))
-                    .unwrap_or(false);
-                frames.push(Time {
-                    frame: keyframe.keyframe.base.time.unwrap_f32(),
-                    hold,
-                });
+                frames.push(conv_time(&keyframe.keyframe.base));
                 values.push(f(&data));
                 // todo: end_value deprecated but should we still append it if it exists?
             }
@@ -174,6 +213,93 @@ pub fn conv_pos<T: Lerp>(
     }
 }
 
+/// Converts the position of a transform.
+pub fn conv_transform_position(value: &AnyTransformP) -> Position {
+    match value {
+        AnyTransformP::Position(position) => conv_position(position),
+        // The z component has no effect on a 2D composition.
+        AnyTransformP::SplitPosition(SplitVector { x, y, .. }) => {
+            Position::SplitValues((conv_scalar(x), conv_scalar(y)))
+        }
+    }
+}
+
+/// Converts the rotation of a transform, defaulting to no rotation.
+pub fn conv_rotation(value: Option<&AnyTransformR>) -> Rotation {
+    match value {
+        None => Rotation::Value(Value::Fixed(0.0)),
+        Some(AnyTransformR::Rotation(rotation)) => {
+            Rotation::Value(conv_scalar(rotation))
+        }
+        Some(AnyTransformR::SplitRotation {
+            x_rotation,
+            y_rotation,
+            z_rotation,
+            orientation,
+        }) => Rotation::Split {
+            x: conv_scalar(x_rotation.as_ref().unwrap_or(&FLOAT_VALUE_ZERO)),
+            y: conv_scalar(y_rotation.as_ref().unwrap_or(&FLOAT_VALUE_ZERO)),
+            z: conv_scalar(z_rotation.as_ref().unwrap_or(&FLOAT_VALUE_ZERO)),
+            orientation: conv_multi(
+                orientation.as_ref().unwrap_or(&MULTIDIM_ZERO),
+                |x| {
+                    [
+                        x.first().copied().unwrap_or(0.0),
+                        x.get(1).copied().unwrap_or(0.0),
+                        x.get(2).copied().unwrap_or(0.0),
+                    ]
+                },
+            ),
+        },
+    }
+}
+
+/// Converts a transform position, keeping the spatial tangents of animated
+/// keyframes so that movement follows the authored motion path.
+pub fn conv_position(
+    value: &schema::animated_properties::position::Position,
+) -> Position {
+    use crate::parser::schema::animated_properties::position::PositionValueK;
+
+    let PositionValueK::Animated(keyframes) = &value.value else {
+        return Position::Value(conv_pos_point(value));
+    };
+    let has_tangents = keyframes.iter().any(|keyframe| {
+        keyframe.value_in_tangent.is_some()
+            || keyframe.value_out_tangent.is_some()
+    });
+    if !has_tangents {
+        return Position::Value(conv_pos_point(value));
+    }
+
+    #[allow(clippy::get_first)]
+    fn to_vec2(value: Option<&Vec<serde_json::Number>>) -> Vec2 {
+        value.map_or(Vec2::ZERO, |x| {
+            Vec2::new(
+                x.get(0).and_then(|n| n.as_f64()).unwrap_or(0.0),
+                x.get(1).and_then(|n| n.as_f64()).unwrap_or(0.0),
+            )
+        })
+    }
+
+    let mut times = vec![];
+    let mut values = vec![];
+    let mut in_tangents = vec![];
+    let mut out_tangents = vec![];
+    for keyframe in keyframes {
+        times.push(conv_time(&keyframe.keyframe.base));
+        values.push(to_vec2(Some(&keyframe.keyframe.value)).to_point());
+        in_tangents.push(to_vec2(keyframe.value_in_tangent.as_ref()));
+        out_tangents.push(to_vec2(keyframe.value_out_tangent.as_ref()));
+    }
+    Position::MotionPath(animated::MotionPath {
+        times,
+        values,
+        out_tangents,
+        in_tangents,
+    })
+}
+
 #[allow(clippy::get_first)]
 pub fn conv_pos_point(
     value: &schema::animated_properties::position::Position,
@@ -211,6 +337,27 @@ pub fn conv_color(
     })
 }
 
+/// Parses a `#rrggbb` or `#rgb` hex color string.
+pub fn conv_hex_color(value: &str) -> Option<Color> {
+    let digits = value.trim().strip_prefix('#').unwrap_or(value.trim());
+    let component = |range: std::ops::Range<usize>| {
+        u8::from_str_radix(digits.get(range)?, 16).ok()
+    };
+    match digits.len() {
+        6 => Some(Color::rgb8(
+            component(0..2)?,
+            component(2..4)?,
+            component(4..6)?,
+        )),
+        3 => Some(Color::rgb8(
+            component(0..1)? * 0x11,
+            component(1..2)? * 0x11,
+            component(2..3)? * 0x11,
+        )),
+        _ => None,
+    }
+}
+
 #[allow(clippy::get_first)]
 pub fn conv_vec2(value: &MultiDimensional) -> Value<Vec2> {
     conv_multi(value, |x| {
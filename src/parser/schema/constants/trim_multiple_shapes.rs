@@ -4,8 +4,9 @@ use serde_repr::{Deserialize_repr, Serialize_repr};
 #[repr(u8)]
 #[derive(Deserialize_repr, Serialize_repr, Debug, Clone, PartialEq)]
 pub enum TrimMultipleShapes {
-    ///
-    Individually = 1,
-    ///
-    Simultaneously = 2,
+    /// Every shape is trimmed to the same segment.
+    Simultaneously = 1,
+    /// Shapes are trimmed one after another, as if joined into a single
+    /// path.
+    Individually = 2,
 }
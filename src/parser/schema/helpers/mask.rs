@@ -2,7 +2,8 @@ use crate::parser::{
     breadcrumb::{Breadcrumb, ValueType},
     schema::{
         animated_properties::{
-            shape_property::ShapeProperty, value::FloatValue,
+            multi_dimensional::MultiDimensional, shape_property::ShapeProperty,
+            value::FloatValue,
         },
         constants::mask_mode::MaskMode,
     },
@@ -53,6 +54,11 @@ pub struct Mask {
     #[serde(rename = "x")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub expand: Option<FloatValue>,
+
+    /// Feather, horizontally and vertically
+    #[serde(rename = "f")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub feather: Option<MultiDimensional>,
 }
 
 impl Mask {
@@ -87,6 +93,10 @@ impl Mask {
             .extract_obj(breadcrumb, "x")
             .and_then(|obj| FloatValue::from_obj(breadcrumb, &obj))
             .ok();
+        let feather = obj
+            .extract_obj(breadcrumb, "f")
+            .and_then(|obj| MultiDimensional::from_obj(breadcrumb, &obj))
+            .ok();
 
         Ok(Mask {
             name,
@@ -96,6 +106,7 @@ impl Mask {
             opacity,
             mode,
             expand,
+            feather,
         })
     }
 }
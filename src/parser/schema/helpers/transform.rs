@@ -22,10 +22,9 @@ pub struct Transform {
     #[serde(rename = "s")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub scale: Option<MultiDimensional>,
-    /// Rotation in degrees, clockwise
-    // todo: need untagged enum for split vector variant
-    #[serde(rename = "r")]
-    #[serde(skip_serializing_if = "Option::is_none")]
+    /// Rotation in degrees, clockwise. Either stored in "r", or split into
+    /// "rx", "ry", "rz" and "or" for 3D layers.
+    #[serde(flatten, with = "any_transform_r")]
     pub rotation: Option<AnyTransformR>,
     /// Skew amount as an angle in degrees
     #[serde(rename = "sk")]
@@ -108,20 +107,24 @@ pub enum AnyTransformP {
 pub enum AnyTransformR {
     /// Rotation in degrees, clockwise
     Rotation(FloatValue),
-    /// Split rotation components
+    /// Split rotation components. Missing components default to 0.
     SplitRotation {
         /// Split rotation X component.
-        #[serde(rename = "x")]
-        x_rotation: FloatValue,
+        #[serde(rename = "rx")]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        x_rotation: Option<FloatValue>,
         /// Split rotation Y component.
-        #[serde(rename = "y")]
-        y_rotation: FloatValue,
+        #[serde(rename = "ry")]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        y_rotation: Option<FloatValue>,
         /// Split rotation component, equivalent to r when not split.
-        #[serde(rename = "z")]
-        z_rotation: FloatValue,
+        #[serde(rename = "rz")]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        z_rotation: Option<FloatValue>,
         /// Orientation
         #[serde(rename = "or")]
-        orientation: MultiDimensional,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        orientation: Option<MultiDimensional>,
     },
 }
 
@@ -131,36 +134,158 @@ impl AnyTransformR {
         obj: &serde_json::map::Map<String, Value>,
     ) -> Result<Self, Error> {
         breadcrumb.enter_unnamed(ValueType::Rotation);
-        let rotation = if let Some(rotation) = obj
+        let rotation = obj
             .extract_obj(breadcrumb, "r")
             .and_then(|obj| FloatValue::from_obj(breadcrumb, &obj))
-            .ok()
-            .map(AnyTransformR::Rotation)
-        {
-            rotation
-        } else {
-            let x_rotation = obj
-                .extract_obj(breadcrumb, "rx")
-                .and_then(|obj| FloatValue::from_obj(breadcrumb, &obj))?;
-            let y_rotation = obj
-                .extract_obj(breadcrumb, "ry")
-                .and_then(|obj| FloatValue::from_obj(breadcrumb, &obj))?;
-            let z_rotation = obj
-                .extract_obj(breadcrumb, "rz")
-                .and_then(|obj| FloatValue::from_obj(breadcrumb, &obj))?;
-            let orientation = obj
-                .extract_obj(breadcrumb, "or")
-                .and_then(|obj| MultiDimensional::from_obj(breadcrumb, &obj))?;
-            // Split should always have the value "1", otherwise it is not a split vector object
-            AnyTransformR::SplitRotation {
+            .map(AnyTransformR::Rotation);
+        let rotation = match rotation {
+            Ok(rotation) => Ok(rotation),
+            Err(error) => {
+                let x_rotation = obj
+                    .extract_obj(breadcrumb, "rx")
+                    .and_then(|obj| FloatValue::from_obj(breadcrumb, &obj))
+                    .ok();
+                let y_rotation = obj
+                    .extract_obj(breadcrumb, "ry")
+                    .and_then(|obj| FloatValue::from_obj(breadcrumb, &obj))
+                    .ok();
+                let z_rotation = obj
+                    .extract_obj(breadcrumb, "rz")
+                    .and_then(|obj| FloatValue::from_obj(breadcrumb, &obj))
+                    .ok();
+                let orientation = obj
+                    .extract_obj(breadcrumb, "or")
+                    .and_then(|obj| {
+                        MultiDimensional::from_obj(breadcrumb, &obj)
+                    })
+                    .ok();
+                if x_rotation.is_some()
+                    || y_rotation.is_some()
+                    || z_rotation.is_some()
+                    || orientation.is_some()
+                {
+                    Ok(AnyTransformR::SplitRotation {
+                        x_rotation,
+                        y_rotation,
+                        z_rotation,
+                        orientation,
+                    })
+                } else {
+                    Err(error)
+                }
+            }
+        };
+
+        breadcrumb.exit();
+        rotation
+    }
+}
+
+/// Serde helpers for [`Transform::rotation`], whose keys sit directly in the
+/// transform object rather than in a nested object.
+mod any_transform_r {
+    use super::AnyTransformR;
+    use crate::parser::schema::animated_properties::{
+        multi_dimensional::MultiDimensional, value::FloatValue,
+    };
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Serialize)]
+    struct RotationKeysRef<'a> {
+        #[serde(rename = "r")]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        rotation: Option<&'a FloatValue>,
+        #[serde(rename = "rx")]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        x_rotation: Option<&'a FloatValue>,
+        #[serde(rename = "ry")]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        y_rotation: Option<&'a FloatValue>,
+        #[serde(rename = "rz")]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        z_rotation: Option<&'a FloatValue>,
+        #[serde(rename = "or")]
+        #[serde(skip_serializing_if = "Option::is_none")]
+        orientation: Option<&'a MultiDimensional>,
+    }
+
+    #[derive(Deserialize)]
+    struct RotationKeys {
+        #[serde(rename = "r")]
+        rotation: Option<FloatValue>,
+        #[serde(rename = "rx")]
+        x_rotation: Option<FloatValue>,
+        #[serde(rename = "ry")]
+        y_rotation: Option<FloatValue>,
+        #[serde(rename = "rz")]
+        z_rotation: Option<FloatValue>,
+        #[serde(rename = "or")]
+        orientation: Option<MultiDimensional>,
+    }
+
+    pub fn serialize<S: Serializer>(
+        value: &Option<AnyTransformR>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let keys = match value {
+            None => RotationKeysRef {
+                rotation: None,
+                x_rotation: None,
+                y_rotation: None,
+                z_rotation: None,
+                orientation: None,
+            },
+            Some(AnyTransformR::Rotation(rotation)) => RotationKeysRef {
+                rotation: Some(rotation),
+                x_rotation: None,
+                y_rotation: None,
+                z_rotation: None,
+                orientation: None,
+            },
+            Some(AnyTransformR::SplitRotation {
                 x_rotation,
                 y_rotation,
                 z_rotation,
                 orientation,
-            }
+            }) => RotationKeysRef {
+                rotation: None,
+                x_rotation: x_rotation.as_ref(),
+                y_rotation: y_rotation.as_ref(),
+                z_rotation: z_rotation.as_ref(),
+                orientation: orientation.as_ref(),
+            },
         };
+        keys.serialize(serializer)
+    }
 
-        breadcrumb.exit();
-        Ok(rotation)
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Option<AnyTransformR>, D::Error> {
+        let keys = RotationKeys::deserialize(deserializer)?;
+        Ok(match keys {
+            RotationKeys {
+                rotation: Some(rotation),
+                ..
+            } => Some(AnyTransformR::Rotation(rotation)),
+            RotationKeys {
+                x_rotation: None,
+                y_rotation: None,
+                z_rotation: None,
+                orientation: None,
+                ..
+            } => None,
+            RotationKeys {
+                x_rotation,
+                y_rotation,
+                z_rotation,
+                orientation,
+                ..
+            } => Some(AnyTransformR::SplitRotation {
+                x_rotation,
+                y_rotation,
+                z_rotation,
+                orientation,
+            }),
+        })
     }
 }
@@ -2,12 +2,17 @@ use super::shape_element::ShapeElement;
 use crate::parser::schema::animated_properties::{
     multi_dimensional::MultiDimensional, position::Position,
 };
+use crate::parser::schema::constants::shape_direction::ShapeDirection;
 use serde::{Deserialize, Serialize};
 
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 pub struct EllipseShape {
     #[serde(flatten)]
     pub shape_element: ShapeElement,
+    /// Direction the shape is drawn as, mostly relevant when using trim path
+    #[serde(rename = "d")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub direction: Option<ShapeDirection>,
     /// Position
     #[serde(rename = "p")]
     pub position: Position,
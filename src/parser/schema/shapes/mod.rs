@@ -18,13 +18,13 @@ pub mod transform;
 pub mod trim;
 // todo pub mod stroke_dash;
 // todo pub mod shape_list;
-// todo pub mod zig_zag;
+pub mod zig_zag;
 // todo pub mod no_style;
 pub mod base_stroke;
-// todo pub mod twist;
-// todo pub mod rounded_corners;
 pub mod gradient;
 pub mod gradient_fill;
+pub mod rounded_corners;
+pub mod twist;
 // todo pub mod modifier;
 
 use self::gradient::Gradient;
@@ -34,9 +34,12 @@ use self::path::PathShape;
 use self::shape_element::ShapeElement;
 use self::{
     fill::FillShape, merge::MergeShape, offset_path::OffsetPathShape,
-    pucker_bloat::PuckerBloatShape, rectangle::RectangleShape,
-    repeater::RepeaterShape, stroke::StrokeShape, transform::TransformShape,
-    trim::TrimShape,
+    polystar::PolyStarShape, pucker_bloat::PuckerBloatShape,
+    rectangle::RectangleShape, repeater::RepeaterShape,
+    repeater_transform::RepeaterTransformShape,
+    rounded_corners::RoundedCornersShape, stroke::StrokeShape,
+    transform::TransformShape, trim::TrimShape, twist::TwistShape,
+    zig_zag::ZigZagShape,
 };
 use crate::parser::breadcrumb::Breadcrumb;
 use crate::parser::{breadcrumb::ValueType, util::MapExt, Error};
@@ -66,6 +69,9 @@ pub enum AnyShape {
     /// An ellipse, defined by its center point and width and height.
     #[serde(rename = "el")]
     Ellipse(EllipseShape),
+    /// A regular polygon or star.
+    #[serde(rename = "sr")]
+    PolyStar(PolyStarShape),
     #[serde(rename = "tr")]
     Transform(TransformShape),
     #[serde(rename = "st")]
@@ -88,11 +94,14 @@ pub enum AnyShape {
     GradientFill(GradientFillShape),
     #[serde(rename = "gs")]
     GradientStroke(GradientStrokeShape),
+    #[serde(rename = "zz")]
+    ZigZag(ZigZagShape),
+    #[serde(rename = "tw")]
+    Twist(TwistShape),
+    #[serde(rename = "rd")]
+    RoundedCorners(RoundedCornersShape),
     // TODO: model other shapes
-    // todo ZigZag(zig_zag),
     // todo no_style(no_style),
-    // todo Twist(twist),
-    // todo RoundedCorners(rounded_corners),
 }
 
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
@@ -156,6 +165,9 @@ impl AnyShape {
         let shape = match shape_type {
             ShapeType::Ellipse => AnyShape::Ellipse(EllipseShape {
                 shape_element,
+                direction: root
+                    .extract_type(breadcrumb, "d", ValueType::EnumInt)
+                    .ok(),
                 position: Position::from_obj(
                     breadcrumb,
                     &root.extract_obj(breadcrumb, "p")?,
@@ -165,8 +177,50 @@ impl AnyShape {
                     &root.extract_obj(breadcrumb, "s")?,
                 )?,
             }),
+            ShapeType::PolyStar => AnyShape::PolyStar(PolyStarShape {
+                shape_element,
+                direction: root
+                    .extract_type(breadcrumb, "d", ValueType::EnumInt)
+                    .ok(),
+                position: Position::from_obj(
+                    breadcrumb,
+                    &root.extract_obj(breadcrumb, "p")?,
+                )?,
+                outer_radius: FloatValue::from_obj(
+                    breadcrumb,
+                    &root.extract_obj(breadcrumb, "or")?,
+                )?,
+                outer_roundness: FloatValue::from_obj(
+                    breadcrumb,
+                    &root.extract_obj(breadcrumb, "os")?,
+                )?,
+                rotation: FloatValue::from_obj(
+                    breadcrumb,
+                    &root.extract_obj(breadcrumb, "r")?,
+                )?,
+                points: FloatValue::from_obj(
+                    breadcrumb,
+                    &root.extract_obj(breadcrumb, "pt")?,
+                )?,
+                star_type: root.extract_type(
+                    breadcrumb,
+                    "sy",
+                    ValueType::EnumInt,
+                )?,
+                inner_radius: root
+                    .extract_obj(breadcrumb, "ir")
+                    .and_then(|obj| FloatValue::from_obj(breadcrumb, &obj))
+                    .ok(),
+                inner_roundness: root
+                    .extract_obj(breadcrumb, "is")
+                    .and_then(|obj| FloatValue::from_obj(breadcrumb, &obj))
+                    .ok(),
+            }),
             ShapeType::Rectangle => AnyShape::Rectangle(RectangleShape {
                 shape_element,
+                direction: root
+                    .extract_type(breadcrumb, "d", ValueType::EnumInt)
+                    .ok(),
                 position: Position::from_obj(
                     breadcrumb,
                     &root.extract_obj(breadcrumb, "p")?,
@@ -274,6 +328,73 @@ impl AnyShape {
                     .extract_type(breadcrumb, "mm", ValueType::EnumInt)
                     .ok(),
             }),
+            ShapeType::OffsetPath => AnyShape::OffsetPath(OffsetPathShape {
+                shape_element,
+                amount: root
+                    .extract_obj(breadcrumb, "a")
+                    .and_then(|obj| FloatValue::from_obj(breadcrumb, &obj))
+                    .ok(),
+                line_join: root
+                    .extract_type(breadcrumb, "lj", ValueType::EnumInt)
+                    .ok(),
+                miter_limit: root
+                    .extract_obj(breadcrumb, "ml")
+                    .and_then(|obj| FloatValue::from_obj(breadcrumb, &obj))
+                    .ok(),
+            }),
+            ShapeType::PuckerBloat => AnyShape::PuckerBloat(PuckerBloatShape {
+                shape_element,
+                amount: root
+                    .extract_obj(breadcrumb, "a")
+                    .and_then(|obj| FloatValue::from_obj(breadcrumb, &obj))
+                    .ok(),
+            }),
+            ShapeType::ZigZag => AnyShape::ZigZag(ZigZagShape {
+                shape_element,
+                frequency: root
+                    .extract_obj(breadcrumb, "r")
+                    .and_then(|obj| FloatValue::from_obj(breadcrumb, &obj))?,
+                amplitude: root
+                    .extract_obj(breadcrumb, "s")
+                    .and_then(|obj| FloatValue::from_obj(breadcrumb, &obj))?,
+                point_type: root
+                    .extract_obj(breadcrumb, "pt")
+                    .and_then(|obj| FloatValue::from_obj(breadcrumb, &obj))?,
+            }),
+            ShapeType::Twist => AnyShape::Twist(TwistShape {
+                shape_element,
+                angle: root
+                    .extract_obj(breadcrumb, "a")
+                    .and_then(|obj| FloatValue::from_obj(breadcrumb, &obj))?,
+                center: root.extract_obj(breadcrumb, "c").and_then(|obj| {
+                    MultiDimensional::from_obj(breadcrumb, &obj)
+                })?,
+            }),
+            ShapeType::RoundedCorners => {
+                AnyShape::RoundedCorners(RoundedCornersShape {
+                    shape_element,
+                    radius: root.extract_obj(breadcrumb, "r").and_then(
+                        |obj| FloatValue::from_obj(breadcrumb, &obj),
+                    )?,
+                })
+            }
+            ShapeType::Repeater => AnyShape::Repeater(RepeaterShape {
+                shape_element,
+                copies: root
+                    .extract_obj(breadcrumb, "c")
+                    .and_then(|obj| FloatValue::from_obj(breadcrumb, &obj))?,
+                offset: root
+                    .extract_obj(breadcrumb, "o")
+                    .and_then(|obj| FloatValue::from_obj(breadcrumb, &obj))
+                    .ok(),
+                composite: root
+                    .extract_type(breadcrumb, "m", ValueType::EnumInt)
+                    .ok(),
+                transform: RepeaterTransformShape::from_obj(
+                    breadcrumb,
+                    &root.extract_obj(breadcrumb, "tr")?,
+                )?,
+            }),
             ShapeType::GradientStroke => AnyShape::GradientStroke(
                 GradientStrokeShape::from_obj(breadcrumb, root)?,
             ),
@@ -1,3 +1,4 @@
+use super::shape_element::ShapeElement;
 use crate::parser::schema::{
     constants::line_join::LineJoin, shapes::FloatValue,
 };
@@ -6,9 +7,8 @@ use serde::{Deserialize, Serialize};
 /// Interpolates the shape with its center point and bezier tangents with the opposite direction
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 pub struct OffsetPathShape {
-    /// Shape Type
-    #[serde(rename = "ty")]
-    pub shape_type: String,
+    #[serde(flatten)]
+    pub shape_element: ShapeElement,
 
     #[serde(rename = "a")]
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -1,15 +1,19 @@
+use super::shape_element::ShapeElement;
 use crate::parser::schema::{
     animated_properties::{position::Position, value::FloatValue},
-    constants::star_type::StarType,
+    constants::{shape_direction::ShapeDirection, star_type::StarType},
 };
 use serde::{Deserialize, Serialize};
 
 /// Regular polygon or star.
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
-pub struct PolyStar {
-    /// Shape Type
-    #[serde(rename = "ty")]
-    pub shape_type: String,
+pub struct PolyStarShape {
+    #[serde(flatten)]
+    pub shape_element: ShapeElement,
+    /// Direction the shape is drawn as, mostly relevant when using trim path
+    #[serde(rename = "d")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub direction: Option<ShapeDirection>,
     /// Position
     #[serde(rename = "p")]
     pub position: Position,
@@ -30,10 +34,12 @@ pub struct PolyStar {
     pub star_type: StarType,
     /// If sy is 1 (star) you also have attributes defining the inner ends of
     /// the star:
-    /// Points
-    #[serde(rename = "lr")]
+    /// Inner Radius
+    #[serde(rename = "ir")]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub inner_radius: Option<FloatValue>,
-    /// Star type, 1 for Star, 2 for Polygon
-    #[serde(rename = "ls")]
+    /// Inner Roundness as a percentage
+    #[serde(rename = "is")]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub inner_roundness: Option<FloatValue>,
 }
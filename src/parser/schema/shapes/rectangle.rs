@@ -1,6 +1,7 @@
 use crate::parser::schema::animated_properties::{
     multi_dimensional::MultiDimensional, position::Position, value::FloatValue,
 };
+use crate::parser::schema::constants::shape_direction::ShapeDirection;
 use serde::{Deserialize, Serialize};
 
 use super::shape_element::ShapeElement;
@@ -10,6 +11,10 @@ use super::shape_element::ShapeElement;
 pub struct RectangleShape {
     #[serde(flatten)]
     pub shape_element: ShapeElement,
+    /// Direction the shape is drawn as, mostly relevant when using trim path
+    #[serde(rename = "d")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub direction: Option<ShapeDirection>,
     /// Center of the rectangle
     #[serde(rename = "p")]
     pub position: Position,
@@ -5,14 +5,14 @@ use crate::parser::schema::{
 };
 
 use super::repeater_transform::RepeaterTransformShape;
+use super::shape_element::ShapeElement;
 
 /// Duplicates previous shapes in a group
 
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 pub struct RepeaterShape {
-    /// Shape Type
-    #[serde(rename = "ty")]
-    pub shape_type: String,
+    #[serde(flatten)]
+    pub shape_element: ShapeElement,
 
     /// Number of copies
     #[serde(rename = "c")]
@@ -1,16 +1,45 @@
+use crate::parser::schema::helpers::transform::Transform;
 use crate::parser::schema::shapes::FloatValue;
+use crate::parser::{breadcrumb::Breadcrumb, error::Error, util::MapExt};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
-/// Transform used by a repe…equent repeated object.
+/// Transform used by a repeater, the transform is applied to each subsequent
+/// repeated object.
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 pub struct RepeaterTransformShape {
-    /// Transform used by a repeater, the transform is applied to each subsequent repeated object.
+    #[serde(flatten)]
+    pub transform: Transform,
+
+    /// Opacity of the first repeated object
     #[serde(rename = "so")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub start_opacity: Option<FloatValue>,
 
-    // Opacity of the last repeated object
+    /// Opacity of the last repeated object
     #[serde(rename = "eo")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub end_opacity: Option<FloatValue>,
 }
+
+impl RepeaterTransformShape {
+    pub fn from_obj(
+        breadcrumb: &mut Breadcrumb,
+        obj: &serde_json::map::Map<String, Value>,
+    ) -> Result<Self, Error> {
+        let transform = Transform::from_obj(breadcrumb, obj)?;
+        let start_opacity = obj
+            .extract_obj(breadcrumb, "so")
+            .and_then(|obj| FloatValue::from_obj(breadcrumb, &obj))
+            .ok();
+        let end_opacity = obj
+            .extract_obj(breadcrumb, "eo")
+            .and_then(|obj| FloatValue::from_obj(breadcrumb, &obj))
+            .ok();
+        Ok(RepeaterTransformShape {
+            transform,
+            start_opacity,
+            end_opacity,
+        })
+    }
+}
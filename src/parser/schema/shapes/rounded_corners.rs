@@ -0,0 +1,14 @@
+use crate::parser::schema::animated_properties::value::FloatValue;
+use serde::{Deserialize, Serialize};
+
+use super::shape_element::ShapeElement;
+
+/// Rounds the corners of shapes.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct RoundedCornersShape {
+    #[serde(flatten)]
+    pub shape_element: ShapeElement,
+    /// Radius of the rounded corners
+    #[serde(rename = "r")]
+    pub radius: FloatValue,
+}
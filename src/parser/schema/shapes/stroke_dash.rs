@@ -12,20 +12,20 @@ use crate::parser::{
     Error,
 };
 
-/// An item used to described the dashe pattern in a stroked path
+/// An item used to described the dash pattern in a stroked path
 #[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
 pub struct StrokeDash {
     #[serde(flatten)]
-    visual_object: VisualObject,
+    pub visual_object: VisualObject,
 
     #[serde(rename = "n")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    dash_type: Option<StrokeDashType>,
+    pub dash_type: Option<StrokeDashType>,
 
     /// Length of the dash
     #[serde(rename = "v")]
     #[serde(skip_serializing_if = "Option::is_none")]
-    length: Option<FloatValue>,
+    pub length: Option<FloatValue>,
 }
 
 impl StrokeDash {
@@ -0,0 +1,19 @@
+use crate::parser::schema::animated_properties::{
+    multi_dimensional::MultiDimensional, value::FloatValue,
+};
+use serde::{Deserialize, Serialize};
+
+use super::shape_element::ShapeElement;
+
+/// Rotates shapes more sharply in the center than at the edges.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct TwistShape {
+    #[serde(flatten)]
+    pub shape_element: ShapeElement,
+    /// Angle in degrees
+    #[serde(rename = "a")]
+    pub angle: FloatValue,
+    /// Center of the twist
+    #[serde(rename = "c")]
+    pub center: MultiDimensional,
+}
@@ -0,0 +1,20 @@
+use crate::parser::schema::animated_properties::value::FloatValue;
+use serde::{Deserialize, Serialize};
+
+use super::shape_element::ShapeElement;
+
+/// Changes the edges of affected shapes into a series of peaks and valleys.
+#[derive(Deserialize, Serialize, Debug, Clone, PartialEq)]
+pub struct ZigZagShape {
+    #[serde(flatten)]
+    pub shape_element: ShapeElement,
+    /// Number of ridges per segment
+    #[serde(rename = "r")]
+    pub frequency: FloatValue,
+    /// Distance between peaks and troughs
+    #[serde(rename = "s")]
+    pub amplitude: FloatValue,
+    /// Point type, 1 for corner and 2 for smooth
+    #[serde(rename = "pt")]
+    pub point_type: FloatValue,
+}
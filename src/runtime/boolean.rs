@@ -0,0 +1,362 @@
+/*!
+Boolean operations on paths.
+
+Operands are flattened to polygons and every edge is split where it meets
+another edge. Each piece is then classified by sampling the operands on both
+of its sides: pieces that separate the inside of the result from the outside
+are kept, oriented consistently and chained into closed contours.
+*/
+
+use std::collections::HashMap;
+use vello::kurbo::{self, PathEl, Point, Vec2};
+
+/// Tolerance for parameters along an edge.
+const PARAM_EPSILON: f64 = 1e-9;
+
+/// Results of path operations keyed on their parameters and operands.
+///
+/// Operations on geometry that is not animated have the same inputs on
+/// every frame, so they are only computed once. Results that are not used
+/// during a frame are evicted at the end of it.
+#[derive(Default)]
+pub struct Cache {
+    entries: HashMap<Vec<u64>, CacheEntry>,
+    /// Scratch buffer for the key of a lookup.
+    key: Vec<u64>,
+}
+
+struct CacheEntry {
+    elements: Vec<PathEl>,
+    is_used: bool,
+}
+
+impl Cache {
+    /// Appends the result of an operation with the given parameters and
+    /// operands to `out`. The result is computed by `compute` if it is not
+    /// cached.
+    pub fn get_or_compute(
+        &mut self,
+        params: &[f64],
+        operands: &[&[PathEl]],
+        out: &mut Vec<PathEl>,
+        compute: impl FnOnce(&mut Vec<PathEl>),
+    ) {
+        self.key.clear();
+        self.key.extend(params.iter().map(|param| param.to_bits()));
+        for operand in operands {
+            self.key.push(operand.len() as u64);
+            for el in operand.iter() {
+                let (tag, points) = match el {
+                    PathEl::MoveTo(p0) => (0, [*p0, Point::ZERO, Point::ZERO]),
+                    PathEl::LineTo(p0) => (1, [*p0, Point::ZERO, Point::ZERO]),
+                    PathEl::QuadTo(p0, p1) => (2, [*p0, *p1, Point::ZERO]),
+                    PathEl::CurveTo(p0, p1, p2) => (3, [*p0, *p1, *p2]),
+                    PathEl::ClosePath => (4, [Point::ZERO; 3]),
+                };
+                self.key.push(tag);
+                for point in points {
+                    self.key.extend([point.x.to_bits(), point.y.to_bits()]);
+                }
+            }
+        }
+        if let Some(entry) = self.entries.get_mut(self.key.as_slice()) {
+            entry.is_used = true;
+            out.extend_from_slice(&entry.elements);
+            return;
+        }
+        let start = out.len();
+        compute(out);
+        self.entries.insert(
+            self.key.clone(),
+            CacheEntry {
+                elements: out[start..].to_vec(),
+                is_used: true,
+            },
+        );
+    }
+
+    /// Removes the results that were not used since the previous call.
+    pub fn evict_unused(&mut self) {
+        self.entries
+            .retain(|_, entry| std::mem::replace(&mut entry.is_used, false));
+    }
+}
+
+/// Combines the operand paths and appends the outline of the result to
+/// `out`.
+///
+/// Each operand is filled with the non-zero rule. The `inside` function
+/// receives whether a point is inside each of the operands and returns
+/// whether the point is inside the result. Curves are flattened with the
+/// given tolerance.
+pub fn combine(
+    operands: &[&[PathEl]],
+    tolerance: f64,
+    inside: impl Fn(&[bool]) -> bool,
+    out: &mut Vec<PathEl>,
+) {
+    let edges = flatten(operands, tolerance);
+    if edges.is_empty() {
+        return;
+    }
+    let bounds = edges.iter().fold(
+        kurbo::Rect::from_points(edges[0].p0, edges[0].p1),
+        |r, e| r.union_pt(e.p0).union_pt(e.p1),
+    );
+    // Offset of the sample points from an edge.
+    let sample_offset = 1e-6 * (1.0 + bounds.width().max(bounds.height()));
+    let mut windings = vec![0; operands.len()];
+    let mut flags = vec![false; operands.len()];
+    let mut is_inside = |point: Point| {
+        windings.iter_mut().for_each(|winding| *winding = 0);
+        for edge in &edges {
+            windings[edge.operand] += edge.winding(point);
+        }
+        for (flag, winding) in flags.iter_mut().zip(&windings) {
+            *flag = *winding != 0;
+        }
+        inside(&flags)
+    };
+    // Keep the pieces on the boundary of the result with the inside on the
+    // left.
+    let mut boundary = vec![];
+    let mut outgoing: HashMap<Key, Vec<usize>> = HashMap::new();
+    for (p0, p1) in split(&edges) {
+        let mid = p0.midpoint(p1);
+        let dir = p1 - p0;
+        let normal = Vec2::new(-dir.y, dir.x) * (sample_offset / dir.hypot());
+        let left = is_inside(mid + normal);
+        let right = is_inside(mid - normal);
+        if left == right {
+            continue;
+        }
+        let (p0, p1) = if left { (p0, p1) } else { (p1, p0) };
+        let ends = outgoing.entry(key(p0)).or_default();
+        // Coincident edges of different operands produce the same piece.
+        if ends.iter().all(|&ix| boundary[ix] != (p0, p1)) {
+            ends.push(boundary.len());
+            boundary.push((p0, p1));
+        }
+    }
+    // Chain the pieces into contours.
+    let mut used = vec![false; boundary.len()];
+    for first in 0..boundary.len() {
+        if used[first] {
+            continue;
+        }
+        let (start, _) = boundary[first];
+        out.push(PathEl::MoveTo(start));
+        let mut ix = first;
+        loop {
+            used[ix] = true;
+            let (_, end) = boundary[ix];
+            if end == start {
+                break;
+            }
+            out.push(PathEl::LineTo(end));
+            let next = outgoing.get(&key(end)).and_then(|ends| {
+                ends.iter().copied().find(|&next| !used[next])
+            });
+            let Some(next) = next else {
+                break;
+            };
+            ix = next;
+        }
+        out.push(PathEl::ClosePath);
+    }
+}
+
+/// Line segment of an operand.
+#[derive(Copy, Clone, Debug)]
+struct Edge {
+    p0: Point,
+    p1: Point,
+    operand: usize,
+}
+
+impl Edge {
+    /// Returns the contribution of the edge to the winding number at the
+    /// given point.
+    fn winding(&self, point: Point) -> i32 {
+        let side = (self.p1 - self.p0).cross(point - self.p0);
+        if self.p0.y <= point.y {
+            if self.p1.y > point.y && side > 0.0 {
+                return 1;
+            }
+        } else if self.p1.y <= point.y && side < 0.0 {
+            return -1;
+        }
+        0
+    }
+}
+
+type Key = (u64, u64);
+
+fn key(point: Point) -> Key {
+    // Normalize negative zero so that equal points share a key.
+    ((point.x + 0.0).to_bits(), (point.y + 0.0).to_bits())
+}
+
+/// Flattens the operands into closed polygons.
+fn flatten(operands: &[&[PathEl]], tolerance: f64) -> Vec<Edge> {
+    let mut edges = vec![];
+    for (operand, path) in operands.iter().enumerate() {
+        let mut start = None;
+        let mut last = Point::ZERO;
+        let close = |edges: &mut Vec<Edge>, last: Point, start| {
+            if let Some(start) = start {
+                if last != start {
+                    edges.push(Edge {
+                        p0: last,
+                        p1: start,
+                        operand,
+                    });
+                }
+            }
+        };
+        kurbo::flatten(path.iter().copied(), tolerance, |el| match el {
+            PathEl::MoveTo(p) => {
+                close(&mut edges, last, start);
+                start = Some(p);
+                last = p;
+            }
+            PathEl::LineTo(p) => {
+                if p != last {
+                    edges.push(Edge {
+                        p0: last,
+                        p1: p,
+                        operand,
+                    });
+                }
+                last = p;
+            }
+            PathEl::ClosePath => {
+                close(&mut edges, last, start);
+                last = start.unwrap_or(last);
+            }
+            _ => {}
+        });
+        close(&mut edges, last, start);
+    }
+    edges
+}
+
+/// Splits the edges at all of their intersections. Pieces that share an
+/// intersection share the exact same end point.
+fn split(edges: &[Edge]) -> Vec<(Point, Point)> {
+    // Edges are swept in order of their left side so that only pairs with
+    // overlapping bounds are intersected. Bounds are padded to cover the
+    // tolerance of the intersection.
+    let bounds = edges
+        .iter()
+        .map(|edge| {
+            let pad = PARAM_EPSILON * (edge.p1 - edge.p0).hypot();
+            kurbo::Rect::from_points(edge.p0, edge.p1).inflate(pad, pad)
+        })
+        .collect::<Vec<_>>();
+    let mut order = (0..edges.len()).collect::<Vec<_>>();
+    order.sort_by(|&a, &b| bounds[a].x0.total_cmp(&bounds[b].x0));
+    // Edge index, parameter and point of each split.
+    let mut splits: Vec<(usize, f64, Point)> = vec![];
+    for (k, &first) in order.iter().enumerate() {
+        let first_bounds = bounds[first];
+        for &second in &order[k + 1..] {
+            let second_bounds = bounds[second];
+            if second_bounds.x0 > first_bounds.x1 {
+                break;
+            }
+            if second_bounds.y0 <= first_bounds.y1
+                && second_bounds.y1 >= first_bounds.y0
+            {
+                intersect(
+                    edges,
+                    first.min(second),
+                    first.max(second),
+                    &mut splits,
+                );
+            }
+        }
+    }
+    splits.sort_by(|a, b| a.0.cmp(&b.0).then(a.1.total_cmp(&b.1)));
+    let mut splits = splits.into_iter().peekable();
+    let mut pieces = vec![];
+    for (ix, edge) in edges.iter().enumerate() {
+        let points =
+            std::iter::from_fn(|| splits.next_if(|split| split.0 == ix))
+                .map(|(_, _, point)| point);
+        let mut start = edge.p0;
+        for point in points.chain([edge.p1]) {
+            if point != start {
+                pieces.push((start, point));
+                start = point;
+            }
+        }
+    }
+    pieces
+}
+
+/// Appends the splits of edges `i` and `j` where they meet.
+fn intersect(
+    edges: &[Edge],
+    i: usize,
+    j: usize,
+    splits: &mut Vec<(usize, f64, Point)>,
+) {
+    let (a, b) = (edges[i], edges[j]);
+    let r = a.p1 - a.p0;
+    let s = b.p1 - b.p0;
+    let d = b.p0 - a.p0;
+    let denom = r.cross(s);
+    if denom.abs() <= PARAM_EPSILON * r.hypot() * s.hypot() {
+        // Parallel edges only meet when collinear, in which case each is
+        // split at the end points of the other.
+        if d.cross(r).abs() > PARAM_EPSILON * r.hypot() * d.hypot() {
+            return;
+        }
+        for point in [b.p0, b.p1] {
+            let t = (point - a.p0).dot(r) / r.hypot2();
+            if is_interior(t) {
+                splits.push((i, t, point));
+            }
+        }
+        for point in [a.p0, a.p1] {
+            let u = (point - b.p0).dot(s) / s.hypot2();
+            if is_interior(u) {
+                splits.push((j, u, point));
+            }
+        }
+        return;
+    }
+    let t = d.cross(s) / denom;
+    let u = d.cross(r) / denom;
+    let range = -PARAM_EPSILON..=1.0 + PARAM_EPSILON;
+    if !range.contains(&t) || !range.contains(&u) {
+        return;
+    }
+    // Prefer existing end points to computed intersections.
+    let point = if !is_interior(t) {
+        if t < 0.5 {
+            a.p0
+        } else {
+            a.p1
+        }
+    } else if !is_interior(u) {
+        if u < 0.5 {
+            b.p0
+        } else {
+            b.p1
+        }
+    } else {
+        a.p0 + r * t
+    };
+    if is_interior(t) {
+        splits.push((i, t, point));
+    }
+    if is_interior(u) {
+        splits.push((j, u, point));
+    }
+}
+
+fn is_interior(t: f64) -> bool {
+    t > PARAM_EPSILON && t < 1.0 - PARAM_EPSILON
+}
@@ -1,3 +1,5 @@
+mod boolean;
+mod path;
 mod render;
 
 use std::collections::HashMap;
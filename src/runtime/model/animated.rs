@@ -4,12 +4,272 @@ Representations of animated values.
 
 use super::*;
 
-use kurbo::PathEl;
+use kurbo::{CubicBez, ParamCurve, ParamCurveArclen, PathEl};
 
 #[derive(Clone, Debug)]
 pub enum Position {
     Value(Value<Point>),
     SplitValues((Value<f32>, Value<f32>)),
+    /// Position that follows a curved path between keyframes.
+    MotionPath(MotionPath),
+}
+
+impl Position {
+    /// Returns true if the position is fixed.
+    pub fn is_fixed(&self) -> bool {
+        match self {
+            Self::Value(value) => value.is_fixed(),
+            Self::SplitValues((x_value, y_value)) => {
+                x_value.is_fixed() && y_value.is_fixed()
+            }
+            Self::MotionPath(_) => false,
+        }
+    }
+
+    /// Evaluates the position at the specified frame.
+    pub fn evaluate(&self, frame: f32) -> Point {
+        match self {
+            Self::Value(value) => value.evaluate(frame),
+            Self::SplitValues((x_value, y_value)) => kurbo::Point {
+                x: x_value.evaluate(frame) as f64,
+                y: y_value.evaluate(frame) as f64,
+            },
+            Self::MotionPath(value) => value.evaluate(frame),
+        }
+    }
+
+    /// Returns the direction of travel at the specified frame, or `None` if
+    /// the position has not moved yet.
+    pub fn direction(&self, frame: f32) -> Option<Vec2> {
+        // Same sampling distance as lottie-web.
+        const DELTA: f32 = 0.01;
+        let (start, end) = self.frame_range()?;
+        if end - start < DELTA {
+            return None;
+        }
+        let heading = |frame: f32| {
+            // Outside of the keyframe range, keep the direction at the
+            // nearest end of the animation.
+            let frame = frame.clamp(start + DELTA, end);
+            let direction = self.evaluate(frame) - self.evaluate(frame - DELTA);
+            (direction.hypot2() > 0.0).then_some(direction)
+        };
+        heading(frame).or_else(|| {
+            // While holding or standing still, keep the direction at the end
+            // of the last movement.
+            let mut frames = self
+                .keyframe_times()
+                .into_iter()
+                .flatten()
+                .map(|time| time.frame)
+                .filter(|&keyframe| keyframe <= frame)
+                .collect::<Vec<_>>();
+            frames.sort_by(|a, b| b.total_cmp(a));
+            frames.into_iter().find_map(heading)
+        })
+    }
+
+    /// Returns the range of frames covered by keyframes.
+    fn frame_range(&self) -> Option<(f32, f32)> {
+        let times = self.keyframe_times();
+        let start = times
+            .iter()
+            .filter_map(|times| times.first())
+            .map(|time| time.frame)
+            .reduce(f32::min)?;
+        let end = times
+            .iter()
+            .filter_map(|times| times.last())
+            .map(|time| time.frame)
+            .reduce(f32::max)?;
+        Some((start, end))
+    }
+
+    /// Returns the keyframe times of each animated component.
+    fn keyframe_times(&self) -> [&[Time]; 2] {
+        fn value_times<T: Lerp>(value: &Value<T>) -> &[Time] {
+            match value {
+                Value::Fixed(_) => &[],
+                Value::Animated(animated) => &animated.times,
+            }
+        }
+        match self {
+            Self::Value(value) => [value_times(value), &[]],
+            Self::SplitValues((x_value, y_value)) => {
+                [value_times(x_value), value_times(y_value)]
+            }
+            Self::MotionPath(value) => [&value.times, &[]],
+        }
+    }
+}
+
+/// Animated position that moves along a cubic bezier segment between each
+/// pair of keyframes.
+#[derive(Clone, Debug)]
+pub struct MotionPath {
+    /// Collection of times.
+    pub times: Vec<Time>,
+    /// Position at each keyframe.
+    pub values: Vec<Point>,
+    /// Offset of the control point leaving each keyframe position.
+    pub out_tangents: Vec<Vec2>,
+    /// Offset of the control point entering the next keyframe position.
+    pub in_tangents: Vec<Vec2>,
+}
+
+impl MotionPath {
+    /// Accuracy used when measuring and inverting arc lengths.
+    const ARCLEN_ACCURACY: f64 = 1e-3;
+
+    /// Evaluates the position at the specified frame.
+    pub fn evaluate(&self, frame: f32) -> Point {
+        self.evaluate_inner(frame).unwrap_or_default()
+    }
+
+    fn evaluate_inner(&self, frame: f32) -> Option<Point> {
+        let ([ix0, ix1], t, hold) =
+            Time::frames_and_weight(&self.times, frame)?;
+        let start = *self.values.get(ix0)?;
+        if hold || ix0 == ix1 {
+            return Some(start);
+        }
+        let end = *self.values.get(ix1)?;
+        let Some(segment) = self.segment(ix0, start, end) else {
+            return Some(start.lerp(end, t as f64));
+        };
+        // Keyframe easing describes progress along the path rather than
+        // along the curve parameter, so map the weight through arc length.
+        let t = (t as f64).clamp(0.0, 1.0);
+        let length = segment.arclen(Self::ARCLEN_ACCURACY);
+        let param = segment.inv_arclen(length * t, Self::ARCLEN_ACCURACY);
+        Some(segment.eval(param))
+    }
+
+    /// Returns the curve leaving the keyframe at `index`, or `None` if the
+    /// keyframes are connected by a straight line.
+    fn segment(
+        &self,
+        index: usize,
+        start: Point,
+        end: Point,
+    ) -> Option<CubicBez> {
+        let out_tangent =
+            self.out_tangents.get(index).copied().unwrap_or_default();
+        let in_tangent =
+            self.in_tangents.get(index).copied().unwrap_or_default();
+        if out_tangent == Vec2::ZERO && in_tangent == Vec2::ZERO {
+            return None;
+        }
+        Some(CubicBez::new(
+            start,
+            start + out_tangent,
+            end + in_tangent,
+            end,
+        ))
+    }
+}
+
+/// Animated rotation.
+#[derive(Clone, Debug)]
+pub enum Rotation {
+    /// Rotation around the z axis in degrees.
+    Value(Value<f32>),
+    /// Separate rotations around each axis in degrees, followed by the
+    /// orientation of the layer.
+    Split {
+        x: Value<f32>,
+        y: Value<f32>,
+        z: Value<f32>,
+        orientation: Value<[f64; 3]>,
+    },
+}
+
+impl Rotation {
+    /// Returns true if the rotation is fixed.
+    pub fn is_fixed(&self) -> bool {
+        match self {
+            Self::Value(value) => value.is_fixed(),
+            Self::Split {
+                x,
+                y,
+                z,
+                orientation,
+            } => {
+                x.is_fixed()
+                    && y.is_fixed()
+                    && z.is_fixed()
+                    && orientation.is_fixed()
+            }
+        }
+    }
+
+    /// Evaluates the rotation at the specified frame, adding the auto-orient
+    /// angle in degrees to the rotation around the z axis. Rotations around
+    /// the x and y axes are projected orthographically onto the plane.
+    pub fn evaluate(&self, frame: f32, auto_orient_angle: f64) -> Affine {
+        match self {
+            Self::Value(value) => {
+                let angle = value.evaluate(frame) as f64 + auto_orient_angle;
+                Affine::rotate(angle.to_radians())
+            }
+            Self::Split {
+                x,
+                y,
+                z,
+                orientation,
+            } => {
+                let orientation = orientation.evaluate(frame);
+                // The axis rotations are applied first, in z, y, x order,
+                // followed by the orientation in the same order.
+                let matrix = [
+                    rotation_x(orientation[0]),
+                    rotation_y(orientation[1]),
+                    rotation_z(orientation[2]),
+                    rotation_x(x.evaluate(frame) as f64),
+                    rotation_y(y.evaluate(frame) as f64),
+                    rotation_z(z.evaluate(frame) as f64 + auto_orient_angle),
+                ]
+                .into_iter()
+                .reduce(|a, b| mul_3x3(&a, &b))
+                .unwrap();
+                Affine::new([
+                    matrix[0][0],
+                    matrix[1][0],
+                    matrix[0][1],
+                    matrix[1][1],
+                    0.0,
+                    0.0,
+                ])
+            }
+        }
+    }
+}
+
+type Matrix3 = [[f64; 3]; 3];
+
+fn mul_3x3(a: &Matrix3, b: &Matrix3) -> Matrix3 {
+    let mut result = [[0.0; 3]; 3];
+    for (i, row) in result.iter_mut().enumerate() {
+        for (j, value) in row.iter_mut().enumerate() {
+            *value = (0..3).map(|k| a[i][k] * b[k][j]).sum();
+        }
+    }
+    result
+}
+
+fn rotation_x(degrees: f64) -> Matrix3 {
+    let (s, c) = degrees.to_radians().sin_cos();
+    [[1.0, 0.0, 0.0], [0.0, c, -s], [0.0, s, c]]
+}
+
+fn rotation_y(degrees: f64) -> Matrix3 {
+    let (s, c) = degrees.to_radians().sin_cos();
+    [[c, 0.0, s], [0.0, 1.0, 0.0], [-s, 0.0, c]]
+}
+
+fn rotation_z(degrees: f64) -> Matrix3 {
+    let (s, c) = degrees.to_radians().sin_cos();
+    [[c, -s, 0.0], [s, c, 0.0], [0.0, 0.0, 1.0]]
 }
 
 /// Animated affine transformation.
@@ -19,8 +279,8 @@ pub struct Transform {
     pub anchor: Value<Point>,
     /// Translation.
     pub position: Position,
-    /// Rotation angle.
-    pub rotation: Value<f32>,
+    /// Rotation.
+    pub rotation: Rotation,
     /// Scale factor.
     pub scale: Value<Vec2>,
     /// Skew factor.
@@ -33,12 +293,7 @@ impl Transform {
     /// Returns true if the transform is fixed.
     pub fn is_fixed(&self) -> bool {
         self.anchor.is_fixed()
-            && match &self.position {
-                Position::Value(value) => value.is_fixed(),
-                Position::SplitValues((x_value, y_value)) => {
-                    x_value.is_fixed() && y_value.is_fixed()
-                }
-            }
+            && self.position.is_fixed()
             && self.rotation.is_fixed()
             && self.scale.is_fixed()
             && self.skew.is_fixed()
@@ -47,15 +302,23 @@ impl Transform {
 
     /// Evaluates the transform at the specified frame.
     pub fn evaluate(&self, frame: f32) -> Affine {
+        self.evaluate_inner(frame, 0.0)
+    }
+
+    /// Evaluates the transform at the specified frame, with an additional
+    /// rotation that orients the content along the direction of travel.
+    pub fn evaluate_auto_oriented(&self, frame: f32) -> Affine {
+        let auto_orient_angle = self
+            .position
+            .direction(frame)
+            .map_or(0.0, |direction| direction.atan2().to_degrees());
+        self.evaluate_inner(frame, auto_orient_angle)
+    }
+
+    fn evaluate_inner(&self, frame: f32, auto_orient_angle: f64) -> Affine {
         let anchor = self.anchor.evaluate(frame);
-        let position = match &self.position {
-            Position::Value(value) => value.evaluate(frame),
-            Position::SplitValues((x_value, y_value)) => kurbo::Point {
-                x: x_value.evaluate(frame) as f64,
-                y: y_value.evaluate(frame) as f64,
-            },
-        };
-        let rotation = self.rotation.evaluate(frame) as f64;
+        let position = self.position.evaluate(frame);
+        let rotation = self.rotation.evaluate(frame, auto_orient_angle);
         let scale = self.scale.evaluate(frame);
         let skew = self.skew.evaluate(frame) as f64;
         let skew_angle = self.skew_angle.evaluate(frame) as f64;
@@ -72,7 +335,7 @@ impl Transform {
             Affine::IDENTITY
         };
         Affine::translate((position.x, position.y))
-            * Affine::rotate(rotation.to_radians())
+            * rotation
             * skew_matrix
             * Affine::scale_non_uniform(scale.x / 100.0, scale.y / 100.0)
             * Affine::translate((-anchor.x, -anchor.y))
@@ -155,14 +418,23 @@ impl Rect {
 /// Animated star or polygon.
 #[derive(Clone, Debug)]
 pub struct Star {
+    /// True if the shape is a polygon rather than a star.
     pub is_polygon: bool,
-    pub direction: f64,
+    /// True if the vertices should be emitted in CCW order.
+    pub is_ccw: bool,
+    /// Center of the shape.
     pub position: Value<Point>,
+    /// Radius of the inner vertices. Unused for polygons.
     pub inner_radius: Value<f32>,
+    /// Roundness of the inner vertices as a percentage. Unused for polygons.
     pub inner_roundness: Value<f32>,
+    /// Radius of the outer vertices.
     pub outer_radius: Value<f32>,
+    /// Roundness of the outer vertices as a percentage.
     pub outer_roundness: Value<f32>,
+    /// Clockwise rotation in degrees.
     pub rotation: Value<f32>,
+    /// Number of points.
     pub points: Value<f32>,
 }
 
@@ -176,6 +448,67 @@ impl Star {
             && self.rotation.is_fixed()
             && self.points.is_fixed()
     }
+
+    /// Evaluates the star at the given frame and emits the elements to the
+    /// specified path.
+    ///
+    /// Vertices are placed like After Effects: the first outer vertex points
+    /// straight up before rotation and, for stars, outer and inner vertices
+    /// alternate. Roundness is emitted as tangents perpendicular to the
+    /// radius, scaled by the length of the perimeter segment.
+    pub fn evaluate(&self, frame: f32, path: &mut Vec<PathEl>) {
+        let center = self.position.evaluate(frame);
+        let points = self.points.evaluate(frame).floor().max(0.0) as usize;
+        if points == 0 {
+            return;
+        }
+        let outer = (
+            self.outer_radius.evaluate(frame) as f64,
+            self.outer_roundness.evaluate(frame) as f64 / 100.0,
+        );
+        let (count, inner) = if self.is_polygon {
+            (points, outer)
+        } else {
+            let inner = (
+                self.inner_radius.evaluate(frame) as f64,
+                self.inner_roundness.evaluate(frame) as f64 / 100.0,
+            );
+            (points * 2, inner)
+        };
+        let direction = if self.is_ccw { -1.0 } else { 1.0 };
+        let angle_step = std::f64::consts::TAU / count as f64 * direction;
+        let start_angle = -std::f64::consts::FRAC_PI_2
+            + (self.rotation.evaluate(frame) as f64).to_radians();
+        // Each vertex with its incoming and outgoing control points.
+        let vertex = |i: usize| {
+            let (radius, roundness) = match i % 2 {
+                0 => outer,
+                _ => inner,
+            };
+            let angle = start_angle + angle_step * i as f64;
+            let offset = Vec2::from_angle(angle) * radius;
+            // Tangent along the direction of travel.
+            let tangent = Vec2::from_angle(angle + std::f64::consts::FRAC_PI_2)
+                * direction;
+            let perimeter_segment =
+                std::f64::consts::TAU * radius / (points * 4) as f64;
+            let handle = tangent * perimeter_segment * roundness;
+            let point = center + offset;
+            (point - handle, point, point + handle)
+        };
+        let (_, mut prev, mut prev_out) = vertex(0);
+        path.push(PathEl::MoveTo(prev));
+        for i in 1..=count {
+            let (next_in, next, next_out) = vertex(i % count);
+            if prev_out == prev && next_in == next {
+                path.push(PathEl::LineTo(next));
+            } else {
+                path.push(PathEl::CurveTo(prev_out, next_in, next));
+            }
+            (prev, prev_out) = (next, next_out);
+        }
+        path.push(PathEl::ClosePath);
+    }
 }
 
 /// Animated cubic spline.
@@ -216,7 +549,7 @@ pub struct Repeater {
     /// Anchor point.
     pub anchor_point: Value<Point>,
     /// Translation.
-    pub position: Value<Point>,
+    pub position: Position,
     /// Rotation in degrees.
     pub rotation: Value<f32>,
     /// Scale.
@@ -225,6 +558,8 @@ pub struct Repeater {
     pub start_opacity: Value<f32>,
     /// Opacity of the last element.
     pub end_opacity: Value<f32>,
+    /// True if each copy is stacked above the previous one.
+    pub is_above: bool,
 }
 
 impl Repeater {
@@ -259,6 +594,7 @@ impl Repeater {
             scale,
             start_opacity,
             end_opacity,
+            is_above: self.is_above,
         }
     }
 
@@ -272,6 +608,153 @@ impl Repeater {
     }
 }
 
+/// Animated trim path modifier.
+#[derive(Clone, Debug)]
+pub struct Trim {
+    /// Start of the segment as a percentage.
+    pub start: Value<f32>,
+    /// End of the segment as a percentage.
+    pub end: Value<f32>,
+    /// Offset of the segment in degrees.
+    pub offset: Value<f32>,
+    /// True if the shapes are trimmed one after another.
+    pub is_individual: bool,
+}
+
+impl Trim {
+    /// Returns true if the trim contains no animated properties.
+    pub fn is_fixed(&self) -> bool {
+        self.start.is_fixed() && self.end.is_fixed() && self.offset.is_fixed()
+    }
+
+    /// Evaluates the trim at the specified frame.
+    pub fn evaluate(&self, frame: f32) -> fixed::Trim {
+        fixed::Trim {
+            start: self.start.evaluate(frame),
+            end: self.end.evaluate(frame),
+            offset: self.offset.evaluate(frame),
+            is_individual: self.is_individual,
+        }
+    }
+
+    /// Converts the animated value to its model representation.
+    pub fn to_model(self) -> super::Trim {
+        if self.is_fixed() {
+            super::Trim::Fixed(self.evaluate(0.0))
+        } else {
+            super::Trim::Animated(self)
+        }
+    }
+}
+
+/// Animated offset path modifier.
+#[derive(Clone, Debug)]
+pub struct OffsetPath {
+    /// Distance to offset the path by.
+    pub amount: Value<f32>,
+    /// Join style for corners.
+    pub join: kurbo::Join,
+    /// Limit for miter joins.
+    pub miter_limit: Value<f32>,
+}
+
+impl OffsetPath {
+    /// Returns true if the offset contains no animated properties.
+    pub fn is_fixed(&self) -> bool {
+        self.amount.is_fixed() && self.miter_limit.is_fixed()
+    }
+
+    /// Evaluates the offset at the specified frame.
+    pub fn evaluate(&self, frame: f32) -> fixed::OffsetPath {
+        fixed::OffsetPath {
+            amount: self.amount.evaluate(frame),
+            join: self.join,
+            miter_limit: self.miter_limit.evaluate(frame),
+        }
+    }
+
+    /// Converts the animated value to its model representation.
+    pub fn to_model(self) -> super::OffsetPath {
+        if self.is_fixed() {
+            super::OffsetPath::Fixed(self.evaluate(0.0))
+        } else {
+            super::OffsetPath::Animated(self)
+        }
+    }
+}
+
+/// Animated zig zag modifier.
+#[derive(Clone, Debug)]
+pub struct ZigZag {
+    /// Number of ridges added to each segment.
+    pub frequency: Value<f32>,
+    /// Distance of the ridges from the path.
+    pub amplitude: Value<f32>,
+    /// Type of the points, 1 for corners and 2 for smooth points that join
+    /// the ridges by curves instead of lines.
+    pub point_type: Value<f32>,
+}
+
+impl ZigZag {
+    /// Returns true if the zig zag contains no animated properties.
+    pub fn is_fixed(&self) -> bool {
+        self.frequency.is_fixed()
+            && self.amplitude.is_fixed()
+            && self.point_type.is_fixed()
+    }
+
+    /// Evaluates the zig zag at the specified frame.
+    pub fn evaluate(&self, frame: f32) -> fixed::ZigZag {
+        fixed::ZigZag {
+            frequency: self.frequency.evaluate(frame),
+            amplitude: self.amplitude.evaluate(frame),
+            is_smooth: self.point_type.evaluate(frame) >= 1.5,
+        }
+    }
+
+    /// Converts the animated value to its model representation.
+    pub fn to_model(self) -> super::ZigZag {
+        if self.is_fixed() {
+            super::ZigZag::Fixed(self.evaluate(0.0))
+        } else {
+            super::ZigZag::Animated(self)
+        }
+    }
+}
+
+/// Animated twist modifier.
+#[derive(Clone, Debug)]
+pub struct Twist {
+    /// Rotation at the center in degrees.
+    pub angle: Value<f32>,
+    /// Center of the rotation.
+    pub center: Value<Point>,
+}
+
+impl Twist {
+    /// Returns true if the twist contains no animated properties.
+    pub fn is_fixed(&self) -> bool {
+        self.angle.is_fixed() && self.center.is_fixed()
+    }
+
+    /// Evaluates the twist at the specified frame.
+    pub fn evaluate(&self, frame: f32) -> fixed::Twist {
+        fixed::Twist {
+            angle: self.angle.evaluate(frame),
+            center: self.center.evaluate(frame),
+        }
+    }
+
+    /// Converts the animated value to its model representation.
+    pub fn to_model(self) -> super::Twist {
+        if self.is_fixed() {
+            super::Twist::Fixed(self.evaluate(0.0))
+        } else {
+            super::Twist::Animated(self)
+        }
+    }
+}
+
 /// Animated stroke properties.
 #[derive(Clone, Debug)]
 pub struct Stroke {
@@ -283,12 +766,18 @@ pub struct Stroke {
     pub miter_limit: Option<f32>,
     /// Cap style.
     pub cap: kurbo::Cap,
+    /// Alternating dash and gap lengths. Empty for a solid stroke.
+    pub dash_pattern: Vec<Value<f32>>,
+    /// Distance into the dash pattern at which the stroke starts.
+    pub dash_offset: Value<f32>,
 }
 
 impl Stroke {
     /// Returns true if the stroke is fixed.
     pub fn is_fixed(&self) -> bool {
         self.width.is_fixed()
+            && self.dash_pattern.iter().all(|dash| dash.is_fixed())
+            && self.dash_offset.is_fixed()
     }
 
     /// Evaluates the stroke at the specified frame.
@@ -300,6 +789,24 @@ impl Stroke {
         if let Some(miter_limit) = self.miter_limit {
             stroke.miter_limit = miter_limit.into();
         }
+        let pattern = self
+            .dash_pattern
+            .iter()
+            .map(|dash| dash.evaluate(frame).max(0.0) as f64);
+        // A pattern without any length would never advance along the path.
+        if pattern.clone().sum::<f64>() > 0.0 {
+            let offset = self.dash_offset.evaluate(frame) as f64;
+            // Like SVG, odd patterns are repeated to alternate dashes and
+            // gaps consistently.
+            let repeat = if self.dash_pattern.len() % 2 == 1 {
+                2
+            } else {
+                1
+            };
+            let pattern =
+                pattern.cycle().take(self.dash_pattern.len() * repeat);
+            stroke = stroke.with_dashes(offset, pattern);
+        }
         stroke
     }
 
@@ -322,6 +829,12 @@ pub struct Gradient {
     pub start_point: Value<Point>,
     /// Ending point.
     pub end_point: Value<Point>,
+    /// Distance of the highlight of a radial gradient from the start point
+    /// as a percentage of the radius.
+    pub highlight_length: Value<f32>,
+    /// Angle of the highlight of a radial gradient in degrees, relative to
+    /// the direction from the start point to the end point.
+    pub highlight_angle: Value<f32>,
     /// Stop offsets and color values.
     pub stops: super::ColorStops,
 }
@@ -331,6 +844,8 @@ impl Gradient {
     pub fn is_fixed(&self) -> bool {
         self.start_point.is_fixed()
             && self.end_point.is_fixed()
+            && self.highlight_length.is_fixed()
+            && self.highlight_angle.is_fixed()
             && self.stops.is_fixed()
     }
 
@@ -340,8 +855,22 @@ impl Gradient {
         let end = self.end_point.evaluate(frame);
         let stops = self.stops.evaluate(frame).to_owned();
         if self.is_radial {
-            let radius = (end.to_vec2() - start.to_vec2()).hypot() as f32;
-            let mut grad = peniko::Gradient::new_radial(start, radius);
+            let direction = end - start;
+            let radius = direction.hypot();
+            // The highlight is the focal point of the gradient. Like other
+            // players, keep it just inside of the end circle.
+            let length = (self.highlight_length.evaluate(frame) as f64 / 100.0)
+                .clamp(-0.99, 0.99);
+            let angle = (self.highlight_angle.evaluate(frame) as f64)
+                .to_radians()
+                + direction.atan2();
+            let focus = start + Vec2::from_angle(angle) * (radius * length);
+            let mut grad = peniko::Gradient::new_two_point_radial(
+                focus,
+                0.0,
+                start,
+                radius as f32,
+            );
             grad.stops = stops;
             grad.into()
         } else {
@@ -352,10 +881,15 @@ impl Gradient {
     }
 }
 
+/// Animated gradient stops.
 #[derive(Clone, Debug)]
 pub struct ColorStops {
+    /// Keyframe times.
     pub frames: Vec<Time>,
+    /// Raw gradient data for each keyframe, as expected by
+    /// [`fixed::color_stops`].
     pub values: Vec<Vec<f32>>,
+    /// Number of color stops.
     pub count: usize,
 }
 
@@ -370,26 +904,21 @@ impl ColorStops {
 
         let v0 = self.values.get(ix0)?;
         let v1 = self.values.get(ix1)?;
-
-        let mut stops: fixed::ColorStops = Default::default();
-        for i in 0..self.count {
-            let j = i * 5;
-            let offset = v0.get(j)?.lerp(v1.get(j)?, t);
-            let t = if hold { 0f32 } else { t };
-
-            let r = v0.get(j + 1)?.lerp(v1.get(j + 1)?, t) as f64;
-            let g = v0.get(j + 2)?.lerp(v1.get(j + 2)?, t) as f64;
-            let b = v0.get(j + 3)?.lerp(v1.get(j + 3)?, t) as f64;
-            let a = v0.get(j + 4)?.lerp(v1.get(j + 4)?, t) as f64;
-
-            stops.push((offset, fixed::Color::rgba(r, g, b, a)).into())
-        }
-        Some(stops)
+        let t = if hold { 0f32 } else { t };
+        // Interpolate the raw data so that color and opacity stops move
+        // independently before they are merged.
+        let values = v0
+            .iter()
+            .zip(v1)
+            .map(|(a, b)| a.lerp(b, t))
+            .collect::<Vec<_>>();
+        Some(fixed::color_stops(&values, self.count))
     }
 }
 
 /// Animated brush.
 #[derive(Clone, Debug)]
+#[allow(clippy::large_enum_variant)]
 pub enum Brush {
     /// Solid color.
     Solid(Value<Color>),
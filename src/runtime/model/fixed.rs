@@ -2,6 +2,7 @@
 Representations of fixed (non-animated) values.
 */
 
+use core::ops::Range;
 use vello::{
     kurbo::{self, Affine, Point, Vec2},
     peniko,
@@ -16,6 +17,70 @@ pub type Color = peniko::Color;
 /// Fixed color stops.
 pub type ColorStops = peniko::ColorStops;
 
+/// Creates color stops from raw gradient data, which holds `count` color
+/// stops of offset, red, green and blue, optionally followed by opacity
+/// stops of offset and alpha.
+///
+/// The result has a stop at each color and opacity stop offset. The color
+/// is interpolated between the color stops and the alpha between the
+/// opacity stops.
+pub fn color_stops(values: &[f32], count: usize) -> ColorStops {
+    let (colors, alphas) = values.split_at((count * 4).min(values.len()));
+    let colors = colors
+        .chunks_exact(4)
+        .map(|chunk| (chunk[0], [chunk[1], chunk[2], chunk[3]]))
+        .collect::<Vec<_>>();
+    let alphas = alphas
+        .chunks_exact(2)
+        .map(|chunk| (chunk[0], [chunk[1]]))
+        .collect::<Vec<_>>();
+    let mut stops = ColorStops::new();
+    let (mut i, mut j) = (0, 0);
+    // Merge the stops in order of their offsets. Stops at the same offset
+    // in both lists produce a single stop.
+    while i < colors.len() || j < alphas.len() {
+        let color_offset = colors.get(i).map_or(f32::INFINITY, |stop| stop.0);
+        let alpha_offset = alphas.get(j).map_or(f32::INFINITY, |stop| stop.0);
+        let offset = color_offset.min(alpha_offset);
+        let [r, g, b] = if color_offset == offset {
+            i += 1;
+            colors[i - 1].1
+        } else {
+            interpolate_stops(&colors, offset).unwrap_or([0.0; 3])
+        };
+        let [a] = if alpha_offset == offset {
+            j += 1;
+            alphas[j - 1].1
+        } else {
+            interpolate_stops(&alphas, offset).unwrap_or([1.0])
+        };
+        let color = Color::rgba(r as f64, g as f64, b as f64, a as f64);
+        stops.push((offset, color).into());
+    }
+    stops
+}
+
+/// Linearly interpolates stop values at the given offset, extending the
+/// first and last values beyond the stops. Returns `None` if there are no
+/// stops.
+fn interpolate_stops<const N: usize>(
+    stops: &[(f32, [f32; N])],
+    offset: f32,
+) -> Option<[f32; N]> {
+    let last = stops.last()?;
+    let Some(ix) = stops.iter().position(|stop| stop.0 >= offset) else {
+        return Some(last.1);
+    };
+    if ix == 0 {
+        return Some(stops[0].1);
+    }
+    let ((a, values_a), (b, values_b)) = (stops[ix - 1], stops[ix]);
+    let t = if b > a { (offset - a) / (b - a) } else { 1.0 };
+    Some(core::array::from_fn(|k| {
+        values_a[k] + (values_b[k] - values_a[k]) * t
+    }))
+}
+
 /// Fixed brush.
 pub type Brush = peniko::Brush;
 
@@ -41,6 +106,8 @@ pub struct Repeater {
     pub start_opacity: f32,
     /// Opacity of the last element.
     pub end_opacity: f32,
+    /// True if each copy is stacked above the previous one.
+    pub is_above: bool,
 }
 
 impl Repeater {
@@ -59,6 +126,77 @@ impl Repeater {
     }
 }
 
+/// Fixed trim path modifier.
+#[derive(Clone, Debug)]
+pub struct Trim {
+    /// Start of the segment as a percentage.
+    pub start: f32,
+    /// End of the segment as a percentage.
+    pub end: f32,
+    /// Offset of the segment in degrees, where 360 is the full length.
+    pub offset: f32,
+    /// True if the shapes are trimmed one after another rather than
+    /// simultaneously.
+    pub is_individual: bool,
+}
+
+impl Trim {
+    /// Returns the visible ranges as fractions of the full length. There are
+    /// two ranges when the offset wraps the segment around the end.
+    pub fn ranges(&self) -> impl Iterator<Item = Range<f64>> {
+        let mut start = (self.start as f64 / 100.0).clamp(0.0, 1.0);
+        let mut end = (self.end as f64 / 100.0).clamp(0.0, 1.0);
+        if start > end {
+            std::mem::swap(&mut start, &mut end);
+        }
+        let ranges = if end - start >= 1.0 {
+            [0.0..1.0, 0.0..0.0]
+        } else {
+            let offset = (self.offset as f64 / 360.0).rem_euclid(1.0);
+            let (start, end) = (start + offset, end + offset);
+            if end <= 1.0 {
+                [start..end, 0.0..0.0]
+            } else if start >= 1.0 {
+                [start - 1.0..end - 1.0, 0.0..0.0]
+            } else {
+                [start..1.0, 0.0..end - 1.0]
+            }
+        };
+        ranges.into_iter().filter(|range| !range.is_empty())
+    }
+}
+
+/// Fixed offset path modifier.
+#[derive(Clone, Debug)]
+pub struct OffsetPath {
+    /// Distance to offset the path by. Negative values inset the path.
+    pub amount: f32,
+    /// Join style for corners.
+    pub join: kurbo::Join,
+    /// Limit for miter joins.
+    pub miter_limit: f32,
+}
+
+/// Fixed zig zag modifier.
+#[derive(Clone, Debug)]
+pub struct ZigZag {
+    /// Number of ridges added to each segment.
+    pub frequency: f32,
+    /// Distance of the ridges from the path.
+    pub amplitude: f32,
+    /// True if the ridges are joined by curves instead of lines.
+    pub is_smooth: bool,
+}
+
+/// Fixed twist modifier.
+#[derive(Clone, Debug)]
+pub struct Twist {
+    /// Rotation at the center in degrees.
+    pub angle: f32,
+    /// Center of the rotation.
+    pub center: Point,
+}
+
 // TODO: probably move this to peniko. The better option is to add an alpha parameter
 // to the draw methods in vello. This is already handled at the encoding level.
 pub(crate) fn brush_with_alpha(brush: &Brush, alpha: f32) -> Brush {
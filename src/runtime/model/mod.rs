@@ -1,6 +1,7 @@
 use vello::kurbo::{self, Affine, PathEl, Point, Shape as _, Size, Vec2};
 use vello::peniko::{self, BlendMode, Color};
 
+use core::f64::consts::{FRAC_PI_2, PI, TAU};
 use core::ops::Range;
 
 pub mod animated;
@@ -9,7 +10,7 @@ pub mod fixed;
 mod spline;
 mod value;
 
-pub use value::{Animated, Lerp, Time, Value, ValueRef};
+pub use value::{Animated, Easing, Lerp, Time, Value, ValueRef};
 
 pub(crate) use spline::SplineToPath;
 
@@ -41,6 +42,10 @@ macro_rules! simple_value {
 simple_value!(Transform);
 simple_value!(Stroke);
 simple_value!(Repeater);
+simple_value!(Trim);
+simple_value!(OffsetPath);
+simple_value!(ZigZag);
+simple_value!(Twist);
 simple_value!(ColorStops);
 
 #[derive(Clone, Debug)]
@@ -70,17 +75,42 @@ impl Brush {
     }
 }
 
+impl Transform {
+    /// Evaluates the transform at the specified frame, rotating to follow
+    /// the direction of travel of the position animation.
+    pub fn evaluate_auto_oriented(&self, frame: f32) -> ValueRef<Affine> {
+        match self {
+            Self::Fixed(value) => ValueRef::Borrowed(value),
+            Self::Animated(value) => {
+                ValueRef::Owned(value.evaluate_auto_oriented(frame))
+            }
+        }
+    }
+}
+
 impl Default for Transform {
     fn default() -> Self {
         Self::Fixed(Affine::IDENTITY)
     }
 }
 
+impl Trim {
+    /// Returns true if the shapes are trimmed one after another rather than
+    /// simultaneously.
+    pub fn is_individual(&self) -> bool {
+        match self {
+            Self::Fixed(value) => value.is_individual,
+            Self::Animated(value) => value.is_individual,
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub enum Geometry {
     Fixed(Vec<PathEl>),
     Rect(animated::Rect),
     Ellipse(animated::Ellipse),
+    Star(animated::Star),
     Spline(animated::Spline),
 }
 
@@ -91,10 +121,23 @@ impl Geometry {
                 path.extend_from_slice(value);
             }
             Self::Rect(value) => {
-                path.extend(value.evaluate(frame).path_elements(0.1));
+                let rect = value.evaluate(frame);
+                if value.is_ccw {
+                    extend_reversed_rect(path, rect);
+                } else {
+                    path.extend(rect.path_elements(0.1));
+                }
             }
             Self::Ellipse(value) => {
-                path.extend(value.evaluate(frame).path_elements(0.1));
+                let ellipse = value.evaluate(frame);
+                if value.is_ccw {
+                    extend_reversed_ellipse(path, ellipse);
+                } else {
+                    path.extend(ellipse.path_elements(0.1));
+                }
+            }
+            Self::Star(value) => {
+                value.evaluate(frame, path);
             }
             Self::Spline(value) => {
                 value.evaluate(frame, path);
@@ -103,14 +146,65 @@ impl Geometry {
     }
 }
 
+/// Appends a rectangle in counter-clockwise order. Like in lottie, it
+/// starts on the right edge at the end of the top right corner.
+fn extend_reversed_rect(path: &mut Vec<PathEl>, rect: kurbo::RoundedRect) {
+    let radius = rect.radii().top_right;
+    let rect = rect.rect();
+    // Center and start angle of each corner.
+    let corners = [
+        (Point::new(rect.x1 - radius, rect.y0 + radius), 0.0),
+        (Point::new(rect.x0 + radius, rect.y0 + radius), -FRAC_PI_2),
+        (Point::new(rect.x0 + radius, rect.y1 - radius), PI),
+        (Point::new(rect.x1 - radius, rect.y1 - radius), FRAC_PI_2),
+    ];
+    for (i, (center, start_angle)) in corners.into_iter().enumerate() {
+        let start = center + Vec2::from_angle(start_angle) * radius;
+        path.push(if i == 0 {
+            PathEl::MoveTo(start)
+        } else {
+            PathEl::LineTo(start)
+        });
+        if radius > 0.0 {
+            let corner = kurbo::Arc {
+                center,
+                radii: Vec2::new(radius, radius),
+                start_angle,
+                sweep_angle: -FRAC_PI_2,
+                x_rotation: 0.0,
+            };
+            path.extend(corner.append_iter(0.1));
+        }
+    }
+    path.push(PathEl::ClosePath);
+}
+
+/// Appends an ellipse in counter-clockwise order. Like in lottie, it starts
+/// at the top.
+fn extend_reversed_ellipse(path: &mut Vec<PathEl>, ellipse: kurbo::Ellipse) {
+    let arc = kurbo::Arc {
+        center: ellipse.center(),
+        radii: ellipse.radii(),
+        start_angle: -FRAC_PI_2,
+        sweep_angle: -TAU,
+        x_rotation: 0.0,
+    };
+    path.extend(arc.path_elements(0.1));
+    path.push(PathEl::ClosePath);
+}
+
 #[derive(Clone, Debug)]
 pub struct Draw {
     /// Parameters for a stroked draw operation.
     pub stroke: Option<Stroke>,
+    /// Fill rule for a filled draw operation.
+    pub fill_rule: peniko::Fill,
     /// Brush for the draw operation.
     pub brush: Brush,
     /// Opacity of the draw operation.
     pub opacity: Value<f32>,
+    /// Blend mode for the draw operation. None is normal blending.
+    pub blend_mode: Option<peniko::BlendMode>,
 }
 
 /// Elements of a shape layer.
@@ -124,17 +218,52 @@ pub enum Shape {
     Draw(Draw),
     /// Repeater element.
     Repeater(Repeater),
+    /// Trims the preceding geometries.
+    Trim(Trim),
+    /// Combines the preceding geometries into a single path.
+    Merge(MergeMode),
+    /// Grows or shrinks the preceding geometries.
+    OffsetPath(OffsetPath),
+    /// Moves the vertices of the preceding geometries toward their center
+    /// and the control points away from it by a percentage.
+    PuckerBloat(Value<f32>),
+    /// Changes the edges of the preceding geometries into ridges.
+    ZigZag(ZigZag),
+    /// Rotates the preceding geometries around a center, more strongly
+    /// closer to the center.
+    Twist(Twist),
+    /// Rounds the corners of the preceding geometries with a radius.
+    RoundedCorners(Value<f32>),
 }
 
-/// Transform and opacity for a shape group.
+/// Boolean operation for merging paths. Every path is combined with the
+/// result of merging the paths before it.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum MergeMode {
+    /// Paths are kept as they are.
+    Merge,
+    /// Union of the paths.
+    Add,
+    /// First path with the following paths removed.
+    Subtract,
+    /// Area covered by all paths.
+    Intersect,
+    /// Area covered by an odd number of paths.
+    ExcludeIntersections,
+}
+
+/// Transform, opacity and blend mode for a shape group.
 #[derive(Clone, Debug)]
 pub struct GroupTransform {
     pub transform: Transform,
     pub opacity: Value<f32>,
+    /// Blend mode for the group, which is composited as a single unit. None
+    /// is normal blending.
+    pub blend_mode: Option<peniko::BlendMode>,
 }
 
 /// Layer in an animation.
-#[derive(Clone, Default, Debug)]
+#[derive(Clone, Debug)]
 pub struct Layer {
     /// Name of the layer.
     pub name: String,
@@ -142,6 +271,9 @@ pub struct Layer {
     pub parent: Option<usize>,
     /// Transform for the entire layer.
     pub transform: Transform,
+    /// True if the layer rotates to follow the direction of its position
+    /// animation.
+    pub auto_orient: bool,
     /// Opacity for the entire layer.
     pub opacity: Value<f32>,
     /// Width of the layer.
@@ -152,7 +284,8 @@ pub struct Layer {
     pub blend_mode: Option<peniko::BlendMode>,
     /// Range of frames in which the layer is active.
     pub frames: Range<f32>,
-    /// Frame time stretch factor.
+    /// Frame time stretch factor. Values above 1 slow down the content of
+    /// instances.
     pub stretch: f32,
     /// Starting frame for the layer (only applied to instances).
     pub start_frame: f32,
@@ -166,6 +299,28 @@ pub struct Layer {
     pub content: Content,
 }
 
+impl Default for Layer {
+    fn default() -> Self {
+        Self {
+            name: Default::default(),
+            parent: None,
+            transform: Default::default(),
+            auto_orient: false,
+            opacity: Default::default(),
+            width: 0,
+            height: 0,
+            blend_mode: None,
+            frames: Default::default(),
+            stretch: 1.0,
+            start_frame: 0.0,
+            masks: Default::default(),
+            is_mask: false,
+            mask_layer: None,
+            content: Default::default(),
+        }
+    }
+}
+
 /// Matte layer mode.
 #[derive(Copy, Clone, PartialEq, Eq, Default, Debug)]
 pub enum Matte {
@@ -180,12 +335,45 @@ pub enum Matte {
 /// Mask for a layer.
 #[derive(Clone, Debug)]
 pub struct Mask {
-    /// Blend mode for the mask.
-    pub mode: peniko::BlendMode,
+    /// How the mask is combined with the masks before it.
+    pub mode: MaskMode,
     /// Geometry that defines the shape of the mask.
     pub geometry: Geometry,
     /// Opacity of the mask.
     pub opacity: Value<f32>,
+    /// True if the mask covers the outside of its geometry.
+    pub is_inverted: bool,
+    /// Distance to grow the geometry by. Negative values shrink it.
+    pub expansion: Value<f32>,
+    /// Horizontal and vertical width of the soft edge of the mask.
+    ///
+    /// The edge is approximated by copies of the geometry offset in a space
+    /// scaled so that both widths are the same. Edges that are not axis
+    /// aligned get a width between the two.
+    pub feather: Value<Vec2>,
+}
+
+/// Operation combining the coverage of a mask with the coverage of the
+/// masks before it.
+///
+/// Coverages are only combined by compositing, so lighten, darken and
+/// difference are approximated. They are exact where either coverage is 0
+/// or 1, such as inside and outside of opaque masks without feathering.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum MaskMode {
+    /// Union of the coverages, `a + b - ab`.
+    Add,
+    /// Removes the mask from the coverage, `a - ab`.
+    Subtract,
+    /// Product of the coverages, `ab`.
+    Intersect,
+    /// Maximum of the coverages, approximated by their union `a + b - ab`.
+    Lighten,
+    /// Minimum of the coverages, approximated by their product `ab`.
+    Darken,
+    /// Absolute difference of the coverages, approximated by the exclusive
+    /// or `a + b - 2ab`.
+    Difference,
 }
 
 /// Content of a layer.
@@ -197,8 +385,13 @@ pub enum Content {
     /// Asset instance with the specified name and time remapping.
     Instance {
         name: String,
-        time_remap: Value<f32>,
+        /// Time in seconds within the asset to display at each frame of the
+        /// layer. The asset plays at its normal rate if none.
+        time_remap: Option<Value<f32>>,
     },
     /// Collection of shapes.
     Shape(Vec<Shape>),
+    /// Rectangle filling the width and height of the layer with a solid
+    /// color.
+    Solid(fixed::Color),
 }
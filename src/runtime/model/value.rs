@@ -56,6 +56,90 @@ impl<'a, T: Clone> ValueRef<'a, T> {
     }
 }
 
+/// Cubic bezier timing function for the segment between two keyframes.
+///
+/// The curve runs from (0, 0) to (1, 1) where the x axis is the normalized
+/// time between the keyframes and the y axis is the interpolation weight.
+#[derive(Copy, Clone, PartialEq, Debug)]
+pub struct Easing {
+    /// Control point leaving the current keyframe.
+    pub out_tangent: kurbo::Point,
+    /// Control point entering the next keyframe.
+    pub in_tangent: kurbo::Point,
+}
+
+impl Easing {
+    /// Linear timing function.
+    pub const LINEAR: Self = Self {
+        out_tangent: kurbo::Point::new(0.0, 0.0),
+        in_tangent: kurbo::Point::new(1.0, 1.0),
+    };
+
+    /// Returns the interpolation weight for the normalized time `t`.
+    pub fn evaluate(&self, t: f32) -> f32 {
+        let x = (t as f64).clamp(0.0, 1.0);
+        let (x1, y1) = (self.out_tangent.x, self.out_tangent.y);
+        let (x2, y2) = (self.in_tangent.x, self.in_tangent.y);
+        if x1 == y1 && x2 == y2 {
+            // The curve is a straight line.
+            return x as f32;
+        }
+        // Both control points are constrained to [0, 1] on the x axis, so the
+        // curve is monotonic in time.
+        let x1 = x1.clamp(0.0, 1.0);
+        let x2 = x2.clamp(0.0, 1.0);
+        let s = solve_cubic_bezier(x1, x2, x);
+        bezier_component(y1, y2, s) as f32
+    }
+}
+
+/// Evaluates one component of a cubic bezier with the end points fixed
+/// at 0 and 1.
+fn bezier_component(c1: f64, c2: f64, s: f64) -> f64 {
+    let u = 1.0 - s;
+    3.0 * u * u * s * c1 + 3.0 * u * s * s * c2 + s * s * s
+}
+
+/// Derivative of [`bezier_component`] with respect to `s`.
+fn bezier_component_derivative(c1: f64, c2: f64, s: f64) -> f64 {
+    let u = 1.0 - s;
+    3.0 * u * u * c1 + 6.0 * u * s * (c2 - c1) + 3.0 * s * s * (1.0 - c2)
+}
+
+/// Finds the curve parameter at which the x component equals `x`.
+fn solve_cubic_bezier(x1: f64, x2: f64, x: f64) -> f64 {
+    const EPSILON: f64 = 1e-6;
+    // Newton-Raphson converges quickly for most curves.
+    let mut s = x;
+    for _ in 0..8 {
+        let error = bezier_component(x1, x2, s) - x;
+        if error.abs() < EPSILON {
+            return s;
+        }
+        let derivative = bezier_component_derivative(x1, x2, s);
+        if derivative.abs() < EPSILON {
+            break;
+        }
+        s -= error / derivative;
+    }
+    // Fall back to bisection for flat regions of the curve.
+    let (mut lo, mut hi) = (0.0, 1.0);
+    s = x;
+    for _ in 0..64 {
+        let value = bezier_component(x1, x2, s);
+        if (value - x).abs() < EPSILON {
+            break;
+        }
+        if value < x {
+            lo = s;
+        } else {
+            hi = s;
+        }
+        s = (lo + hi) * 0.5;
+    }
+    s
+}
+
 /// Time for a particular keyframe, represented as a frame number.
 #[derive(Copy, Clone, Default, Debug)]
 pub struct Time {
@@ -63,6 +147,11 @@ pub struct Time {
     pub frame: f32,
     /// Whether it's a hold frame.
     pub hold: bool,
+    /// Timing function used to reach the next keyframe. Linear if none.
+    pub easing: Option<Easing>,
+    /// Separate timing functions for the x, y and z components of a
+    /// multi-dimensional value. Takes precedence over `easing` when present.
+    pub component_easing: Option<[Easing; 3]>,
 }
 
 impl Time {
@@ -70,6 +159,36 @@ impl Time {
     pub(crate) fn frames_and_weight(
         times: &[Time],
         frame: f32,
+    ) -> Option<([usize; 2], f32, bool)> {
+        let ([ix0, ix1], t, hold) = Self::frames_and_time(times, frame)?;
+        let t = match &times[ix0].easing {
+            Some(easing) => easing.evaluate(t),
+            None => t,
+        };
+        Some(([ix0, ix1], t, hold))
+    }
+
+    /// Returns the frame indices and per-component interpolation weights for
+    /// the given frame, and whether to hold the frame.
+    pub(crate) fn frames_and_weights(
+        times: &[Time],
+        frame: f32,
+    ) -> Option<([usize; 2], [f32; 3], bool)> {
+        let ([ix0, ix1], t, hold) = Self::frames_and_time(times, frame)?;
+        let key = &times[ix0];
+        let weights = match (&key.component_easing, &key.easing) {
+            (Some(easings), _) => easings.map(|easing| easing.evaluate(t)),
+            (None, Some(easing)) => [easing.evaluate(t); 3],
+            (None, None) => [t; 3],
+        };
+        Some(([ix0, ix1], weights, hold))
+    }
+
+    /// Returns the frame indices and normalized linear time between them for
+    /// the given frame, and whether to hold the frame.
+    fn frames_and_time(
+        times: &[Time],
+        frame: f32,
     ) -> Option<([usize; 2], f32, bool)> {
         if times.is_empty() {
             return None;
@@ -92,8 +211,12 @@ impl Time {
         let t0 = times[ix0].frame;
         let t1 = times[ix1].frame;
         let hold = times[ix0].hold;
-        let t = (frame - t0) / (t1 - t0);
-        Some(([ix0, ix1], t.clamp(0.0, 1.0), hold))
+        let t = if t1 > t0 {
+            ((frame - t0) / (t1 - t0)).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        Some(([ix0, ix1], t, hold))
     }
 }
 
@@ -111,15 +234,25 @@ impl<T: Lerp> Animated<T> {
 
     fn evaluate_inner(&self, frame: f32) -> Option<T> {
         let ([ix0, ix1], t, hold) =
-            Time::frames_and_weight(&self.times, frame)?;
-        let t = if hold { 0f32 } else { t };
+            Time::frames_and_weights(&self.times, frame)?;
+        let t = if hold { [0f32; 3] } else { t };
 
-        Some(self.values.get(ix0)?.lerp(self.values.get(ix1)?, t))
+        Some(
+            self.values
+                .get(ix0)?
+                .lerp_components(self.values.get(ix1)?, t),
+        )
     }
 }
 
 pub trait Lerp: Clone + Default {
     fn lerp(&self, other: &Self, t: f32) -> Self;
+
+    /// Interpolates with a separate weight for each of the x, y and z
+    /// components. Values without separable components use the first weight.
+    fn lerp_components(&self, other: &Self, t: [f32; 3]) -> Self {
+        self.lerp(other, t[0])
+    }
 }
 
 impl Lerp for f32 {
@@ -138,12 +271,20 @@ impl Lerp for kurbo::Point {
     fn lerp(&self, other: &Self, t: f32) -> Self {
         Self::new(self.x.lerp(&other.x, t), self.y.lerp(&other.y, t))
     }
+
+    fn lerp_components(&self, other: &Self, t: [f32; 3]) -> Self {
+        Self::new(self.x.lerp(&other.x, t[0]), self.y.lerp(&other.y, t[1]))
+    }
 }
 
 impl Lerp for kurbo::Vec2 {
     fn lerp(&self, other: &Self, t: f32) -> Self {
         Self::new(self.x.lerp(&other.x, t), self.y.lerp(&other.y, t))
     }
+
+    fn lerp_components(&self, other: &Self, t: [f32; 3]) -> Self {
+        Self::new(self.x.lerp(&other.x, t[0]), self.y.lerp(&other.y, t[1]))
+    }
 }
 
 impl Lerp for kurbo::Size {
@@ -153,6 +294,27 @@ impl Lerp for kurbo::Size {
             self.height.lerp(&other.height, t),
         )
     }
+
+    fn lerp_components(&self, other: &Self, t: [f32; 3]) -> Self {
+        Self::new(
+            self.width.lerp(&other.width, t[0]),
+            self.height.lerp(&other.height, t[1]),
+        )
+    }
+}
+
+impl Lerp for [f64; 3] {
+    fn lerp(&self, other: &Self, t: f32) -> Self {
+        self.lerp_components(other, [t; 3])
+    }
+
+    fn lerp_components(&self, other: &Self, t: [f32; 3]) -> Self {
+        [
+            self[0].lerp(&other[0], t[0]),
+            self[1].lerp(&other[1], t[1]),
+            self[2].lerp(&other[2], t[2]),
+        ]
+    }
 }
 
 impl Lerp for peniko::Color {
@@ -0,0 +1,474 @@
+/*!
+Path operations used by shape modifiers.
+*/
+
+use super::boolean;
+use core::ops::Range;
+use vello::kurbo::{
+    self, Affine, Join, ParamCurve, ParamCurveArclen, ParamCurveDeriv, PathEl,
+    PathSeg, Point, Vec2,
+};
+
+/// Accuracy used for arc length computations.
+const ARCLEN_ACCURACY: f64 = 1e-3;
+
+/// Returns an iterator over the subpaths of a path. Each subpath begins
+/// with a move.
+pub fn subpaths(path: &[PathEl]) -> impl Iterator<Item = &[PathEl]> {
+    let mut rest = path;
+    core::iter::from_fn(move || {
+        if rest.is_empty() {
+            return None;
+        }
+        let len = rest[1..]
+            .iter()
+            .position(|el| matches!(el, PathEl::MoveTo(_)))
+            .map_or(rest.len(), |ix| ix + 1);
+        let (subpath, tail) = rest.split_at(len);
+        rest = tail;
+        Some(subpath)
+    })
+}
+
+/// Returns the arc length of a path.
+pub fn length(path: &[PathEl]) -> f64 {
+    kurbo::segments(path.iter().copied())
+        .map(|seg| seg.arclen(ARCLEN_ACCURACY))
+        .sum()
+}
+
+/// Appends the portion of a subpath within the given arc length range to
+/// `out`.
+///
+/// A piece that starts where the previous element in `out` ends continues
+/// that contour rather than starting a new one, so wrapping around the start
+/// of a closed path produces a single contour.
+pub fn trim(
+    subpath: &[PathEl],
+    subpath_length: f64,
+    range: Range<f64>,
+    out: &mut Vec<PathEl>,
+) {
+    if range.start <= 0.0 && range.end >= subpath_length {
+        out.extend_from_slice(subpath);
+        return;
+    }
+    let mut offset = 0.0;
+    for seg in kurbo::segments(subpath.iter().copied()) {
+        let seg_length = seg.arclen(ARCLEN_ACCURACY);
+        let seg_range = offset..offset + seg_length;
+        offset += seg_length;
+        let start = range.start.max(seg_range.start);
+        let end = range.end.min(seg_range.end);
+        if start >= end || seg_length <= 0.0 {
+            continue;
+        }
+        let t0 = if start > seg_range.start {
+            seg.inv_arclen(start - seg_range.start, ARCLEN_ACCURACY)
+        } else {
+            0.0
+        };
+        let t1 = if end < seg_range.end {
+            seg.inv_arclen(end - seg_range.start, ARCLEN_ACCURACY)
+        } else {
+            1.0
+        };
+        let seg = seg.subsegment(t0..t1);
+        if end_point(out) != Some(seg.start()) {
+            out.push(PathEl::MoveTo(seg.start()));
+        }
+        out.push(match seg {
+            PathSeg::Line(line) => PathEl::LineTo(line.p1),
+            PathSeg::Quad(quad) => PathEl::QuadTo(quad.p1, quad.p2),
+            PathSeg::Cubic(cubic) => {
+                PathEl::CurveTo(cubic.p1, cubic.p2, cubic.p3)
+            }
+        });
+    }
+}
+
+/// Appends a subpath with its vertices moved toward their average by
+/// `amount` and its control points moved away from it by the same amount.
+///
+/// All segments are emitted as cubics so that straight edges bend.
+/// Zero length segments, such as the corners of a rectangle without
+/// rounding, are dropped as they would otherwise turn into loops.
+pub fn pucker_bloat(subpath: &[PathEl], amount: f64, out: &mut Vec<PathEl>) {
+    let Some((&PathEl::MoveTo(start), rest)) = subpath.split_first() else {
+        return;
+    };
+    let elements = || {
+        let mut current = start;
+        rest.iter().filter(move |el| {
+            let is_degenerate = match **el {
+                PathEl::LineTo(p) => p == current,
+                PathEl::QuadTo(p1, p2) => p1 == current && p2 == current,
+                PathEl::CurveTo(p1, p2, p3) => {
+                    p1 == current && p2 == current && p3 == current
+                }
+                _ => false,
+            };
+            current = end_point(&[**el]).unwrap_or(start);
+            !is_degenerate
+        })
+    };
+    let (mut sum, mut count, mut last) = (start.to_vec2(), 1, start);
+    for vertex in elements().filter_map(|el| end_point(&[*el])) {
+        sum += vertex.to_vec2();
+        count += 1;
+        last = vertex;
+    }
+    // The end point of a segment that closes the path repeats the start.
+    if count > 1 && last == start {
+        sum -= last.to_vec2();
+        count -= 1;
+    }
+    let center = (sum / count as f64).to_point();
+    let vertex = |p: Point| p.lerp(center, amount);
+    let control = |p: Point| p.lerp(center, -amount);
+    out.push(PathEl::MoveTo(vertex(start)));
+    let mut current = start;
+    for el in elements() {
+        if *el == PathEl::ClosePath && current != start {
+            // The closing line bends like any other edge.
+            out.push(PathEl::CurveTo(
+                control(current),
+                control(start),
+                vertex(start),
+            ));
+        }
+        out.push(match *el {
+            PathEl::MoveTo(p) => PathEl::MoveTo(vertex(p)),
+            PathEl::LineTo(p) => {
+                PathEl::CurveTo(control(current), control(p), vertex(p))
+            }
+            PathEl::QuadTo(p1, p2) => {
+                let c1 = current.lerp(p1, 2.0 / 3.0);
+                let c2 = p2.lerp(p1, 2.0 / 3.0);
+                PathEl::CurveTo(control(c1), control(c2), vertex(p2))
+            }
+            PathEl::CurveTo(p1, p2, p3) => {
+                PathEl::CurveTo(control(p1), control(p2), vertex(p3))
+            }
+            PathEl::ClosePath => PathEl::ClosePath,
+        });
+        current = end_point(&[*el]).unwrap_or(start);
+    }
+}
+
+/// Appends the outline of a path grown by `amount`, or shrunk if `amount`
+/// is negative. Curves are flattened with the given tolerance.
+pub fn offset(
+    path: &[PathEl],
+    amount: f64,
+    join: Join,
+    miter_limit: f64,
+    tolerance: f64,
+    out: &mut Vec<PathEl>,
+) {
+    if amount == 0.0 {
+        out.extend_from_slice(path);
+        return;
+    }
+    // The outline of a stroke twice as wide as the offset is added to grow
+    // the path or removed to shrink it.
+    let style = kurbo::Stroke::new(2.0 * amount.abs())
+        .with_join(join)
+        .with_miter_limit(miter_limit);
+    let outline = kurbo::stroke(
+        path.iter().copied(),
+        &style,
+        &kurbo::StrokeOpts::default(),
+        tolerance,
+    );
+    let is_grow = amount > 0.0;
+    let inside = |flags: &[bool]| {
+        if is_grow {
+            flags[0] || flags[1]
+        } else {
+            flags[0] && !flags[1]
+        }
+    };
+    boolean::combine(&[path, outline.elements()], tolerance, inside, out);
+}
+
+/// Appends a subpath with its edges changed into ridges.
+///
+/// Every vertex and `frequency` evenly spaced points on each segment are
+/// moved by `amplitude` alternately to either side of the path. The points
+/// are joined by lines or, if `is_smooth`, by curves following the path.
+pub fn zig_zag(
+    subpath: &[PathEl],
+    frequency: usize,
+    amplitude: f64,
+    is_smooth: bool,
+    out: &mut Vec<PathEl>,
+) {
+    let is_closed = matches!(subpath.last(), Some(PathEl::ClosePath));
+    let segments = kurbo::segments(subpath.iter().copied())
+        .filter(|seg| !is_degenerate(*seg))
+        .collect::<Vec<_>>();
+    let Some(&last) = segments.last() else {
+        out.extend_from_slice(subpath);
+        return;
+    };
+    // Smooth points have handles along the path, half the distance to the
+    // neighboring points.
+    let handle = |seg: &PathSeg| {
+        if is_smooth {
+            (seg.end() - seg.start()).hypot() / (frequency + 1) as f64 / 2.0
+        } else {
+            0.0
+        }
+    };
+    let mut ridges: Vec<Ridge> = vec![];
+    let mut push = |point: Point, tangent: Vec2, handles: (f64, f64)| {
+        let side = match ridges.len() % 2 {
+            0 => 1.0,
+            _ => -1.0,
+        };
+        let normal = Vec2::new(-tangent.y, tangent.x);
+        ridges.push(Ridge {
+            point: point + normal * side * amplitude,
+            tangent,
+            handles,
+        });
+    };
+    for (i, seg) in segments.iter().enumerate() {
+        let prev = match i {
+            0 if is_closed => Some(&last),
+            0 => None,
+            _ => Some(&segments[i - 1]),
+        };
+        // Vertices are moved along the average of the adjoining tangents.
+        let vertex_tangent = prev
+            .map(|prev| tangent(*prev, 1.0) + tangent(*seg, 0.0))
+            .filter(|sum| sum.hypot2() > 1e-12)
+            .map_or_else(|| tangent(*seg, 0.0), |sum| sum / sum.hypot());
+        push(
+            seg.start(),
+            vertex_tangent,
+            (prev.map_or(0.0, handle), handle(seg)),
+        );
+        for k in 1..=frequency {
+            let t = k as f64 / (frequency + 1) as f64;
+            push(seg.eval(t), tangent(*seg, t), (handle(seg), handle(seg)));
+        }
+    }
+    if !is_closed {
+        push(last.end(), tangent(last, 1.0), (handle(&last), 0.0));
+    }
+    let join = |from: &Ridge, to: &Ridge| {
+        if is_smooth {
+            PathEl::CurveTo(
+                from.point + from.tangent * from.handles.1,
+                to.point - to.tangent * to.handles.0,
+                to.point,
+            )
+        } else {
+            PathEl::LineTo(to.point)
+        }
+    };
+    out.push(PathEl::MoveTo(ridges[0].point));
+    out.extend(ridges.windows(2).map(|pair| join(&pair[0], &pair[1])));
+    if is_closed {
+        out.push(join(&ridges[ridges.len() - 1], &ridges[0]));
+        out.push(PathEl::ClosePath);
+    }
+}
+
+/// Point of a zig zag path with the unit tangent and the lengths of its
+/// incoming and outgoing handles.
+struct Ridge {
+    point: Point,
+    tangent: Vec2,
+    handles: (f64, f64),
+}
+
+/// Appends a path rotated around `center` by `angle` in radians, decreasing
+/// to no rotation at the point farthest from the center.
+pub fn twist(
+    path: &[PathEl],
+    angle: f64,
+    center: Point,
+    out: &mut Vec<PathEl>,
+) {
+    let radius = path
+        .iter()
+        .flat_map(points)
+        .map(|p| (p - center).hypot())
+        .fold(0.0, f64::max);
+    if radius == 0.0 || angle == 0.0 {
+        out.extend_from_slice(path);
+        return;
+    }
+    let rotate = |p: Point| {
+        let offset = p - center;
+        let angle = angle * (1.0 - offset.hypot() / radius);
+        Affine::rotate_about(angle, center) * p
+    };
+    let mut start = Point::ZERO;
+    let mut current = Point::ZERO;
+    for el in path {
+        if *el == PathEl::ClosePath && current != start {
+            // The closing line bends like any other line.
+            out.push(PathEl::CurveTo(
+                rotate(current.lerp(start, 1.0 / 3.0)),
+                rotate(current.lerp(start, 2.0 / 3.0)),
+                rotate(start),
+            ));
+        }
+        out.push(match *el {
+            PathEl::MoveTo(p) => {
+                start = p;
+                PathEl::MoveTo(rotate(p))
+            }
+            // Lines become curves so that they can bend.
+            PathEl::LineTo(p) => PathEl::CurveTo(
+                rotate(current.lerp(p, 1.0 / 3.0)),
+                rotate(current.lerp(p, 2.0 / 3.0)),
+                rotate(p),
+            ),
+            PathEl::QuadTo(p1, p2) => PathEl::QuadTo(rotate(p1), rotate(p2)),
+            PathEl::CurveTo(p1, p2, p3) => {
+                PathEl::CurveTo(rotate(p1), rotate(p2), rotate(p3))
+            }
+            PathEl::ClosePath => PathEl::ClosePath,
+        });
+        current = end_point(&[*el]).unwrap_or(start);
+    }
+}
+
+/// Appends a subpath with its sharp corners replaced by arcs of the given
+/// radius, limited to half the length of the adjoining segments. Corners
+/// are sharp where the handles on both sides coincide with the vertex.
+pub fn round_corners(subpath: &[PathEl], radius: f64, out: &mut Vec<PathEl>) {
+    let is_closed = matches!(subpath.last(), Some(PathEl::ClosePath));
+    let segments = kurbo::segments(subpath.iter().copied())
+        .filter(|seg| !is_degenerate(*seg))
+        .collect::<Vec<_>>();
+    let count = segments.len();
+    if count == 0 || radius <= 0.0 {
+        out.extend_from_slice(subpath);
+        return;
+    }
+    // Parameters on segment k - 1 and segment k where the rounded corner
+    // joining them starts and ends.
+    let corner = |k: usize| {
+        if !is_closed && (k == 0 || k == count) {
+            return None;
+        }
+        let prev = segments[(k + count - 1) % count];
+        let next = segments[k % count];
+        if !is_sharp(prev, true) || !is_sharp(next, false) {
+            return None;
+        }
+        let prev_length = prev.arclen(ARCLEN_ACCURACY);
+        let next_length = next.arclen(ARCLEN_ACCURACY);
+        Some((
+            prev.inv_arclen(
+                prev_length - radius.min(prev_length / 2.0),
+                ARCLEN_ACCURACY,
+            ),
+            next.inv_arclen(radius.min(next_length / 2.0), ARCLEN_ACCURACY),
+        ))
+    };
+    let corners = (0..=count).map(corner).collect::<Vec<_>>();
+    let start = corners[0].map_or(0.0, |(_, t)| t);
+    out.push(PathEl::MoveTo(segments[0].eval(start)));
+    for (k, seg) in segments.iter().enumerate() {
+        let start = corners[k].map_or(0.0, |(_, t)| t);
+        let end = corners[k + 1].map_or(1.0, |(t, _)| t);
+        out.push(match seg.subsegment(start..end) {
+            PathSeg::Line(line) => PathEl::LineTo(line.p1),
+            PathSeg::Quad(quad) => PathEl::QuadTo(quad.p1, quad.p2),
+            PathSeg::Cubic(cubic) => {
+                PathEl::CurveTo(cubic.p1, cubic.p2, cubic.p3)
+            }
+        });
+        if let Some((end, next_start)) = corners[k + 1] {
+            let start = seg.eval(end);
+            let vertex = seg.end();
+            let end = segments[(k + 1) % count].eval(next_start);
+            // Handle length for approximating a quarter circle.
+            const KAPPA: f64 = 0.5519;
+            out.push(PathEl::CurveTo(
+                start.lerp(vertex, KAPPA),
+                end.lerp(vertex, KAPPA),
+                end,
+            ));
+        }
+    }
+    if is_closed {
+        out.push(PathEl::ClosePath);
+    }
+}
+
+/// Returns the unit tangent of a segment at the given parameter.
+fn tangent(seg: PathSeg, t: f64) -> Vec2 {
+    let deriv = match seg {
+        PathSeg::Line(line) => line.p1 - line.p0,
+        PathSeg::Quad(quad) => quad.deriv().eval(t).to_vec2(),
+        PathSeg::Cubic(cubic) => cubic.deriv().eval(t).to_vec2(),
+    };
+    // Handles may coincide with the end points.
+    let deriv = if deriv.hypot2() > 1e-12 {
+        deriv
+    } else {
+        seg.end() - seg.start()
+    };
+    deriv / deriv.hypot()
+}
+
+/// Returns true if the handle at the end, or start if not `at_end`, of a
+/// segment coincides with the end point.
+fn is_sharp(seg: PathSeg, at_end: bool) -> bool {
+    match seg {
+        PathSeg::Line(_) => true,
+        PathSeg::Quad(quad) => {
+            if at_end {
+                quad.p1 == quad.p2
+            } else {
+                quad.p1 == quad.p0
+            }
+        }
+        PathSeg::Cubic(cubic) => {
+            if at_end {
+                cubic.p2 == cubic.p3
+            } else {
+                cubic.p1 == cubic.p0
+            }
+        }
+    }
+}
+
+/// Returns true if all points of the segment are the same.
+fn is_degenerate(seg: PathSeg) -> bool {
+    match seg {
+        PathSeg::Line(line) => line.p0 == line.p1,
+        PathSeg::Quad(quad) => quad.p0 == quad.p1 && quad.p1 == quad.p2,
+        PathSeg::Cubic(cubic) => {
+            cubic.p0 == cubic.p1 && cubic.p1 == cubic.p2 && cubic.p2 == cubic.p3
+        }
+    }
+}
+
+/// Returns the points of a path element.
+fn points(el: &PathEl) -> impl Iterator<Item = Point> {
+    let (points, count) = match *el {
+        PathEl::MoveTo(p) | PathEl::LineTo(p) => ([p, p, p], 1),
+        PathEl::QuadTo(p1, p2) => ([p1, p2, p2], 2),
+        PathEl::CurveTo(p1, p2, p3) => ([p1, p2, p3], 3),
+        PathEl::ClosePath => ([Point::ZERO; 3], 0),
+    };
+    points.into_iter().take(count)
+}
+
+/// Returns the end point of the last element of an open contour.
+fn end_point(path: &[PathEl]) -> Option<Point> {
+    match path.last()? {
+        PathEl::MoveTo(p) | PathEl::LineTo(p) => Some(*p),
+        PathEl::QuadTo(_, p) | PathEl::CurveTo(_, _, p) => Some(*p),
+        PathEl::ClosePath => None,
+    }
+}
@@ -1,8 +1,8 @@
-use super::{model::*, Composition};
+use super::{boolean, model::*, path, Composition};
 use std::ops::Range;
 use vello::{
-    kurbo::{self, Affine, PathEl, Rect},
-    peniko::{self, Fill, Mix},
+    kurbo::{self, Affine, Join, PathEl, Rect, Shape as _, Vec2},
+    peniko::{self, BlendMode, Color, Compose, Fill, Mix},
 };
 
 pub trait RenderSink {
@@ -19,6 +19,7 @@ pub trait RenderSink {
     fn draw(
         &mut self,
         stroke: Option<&fixed::Stroke>,
+        fill: Fill,
         transform: Affine,
         brush: &fixed::Brush,
         shape: &impl kurbo::Shape,
@@ -43,6 +44,7 @@ impl RenderSink for vello::SceneBuilder<'_> {
     fn draw<'b>(
         &mut self,
         stroke: Option<&fixed::Stroke>,
+        fill: Fill,
         transform: Affine,
         brush: &fixed::Brush,
         shape: &impl kurbo::Shape,
@@ -50,7 +52,7 @@ impl RenderSink for vello::SceneBuilder<'_> {
         if let Some(stroke) = stroke {
             self.stroke(stroke, transform, brush, None, shape);
         } else {
-            self.fill(Fill::NonZero, transform, brush, None, shape);
+            self.fill(fill, transform, brush, None, shape);
         }
     }
 }
@@ -59,7 +61,13 @@ impl RenderSink for vello::SceneBuilder<'_> {
 #[derive(Default)]
 pub struct Renderer {
     batch: Batch,
+    /// Results of merges.
+    merges: boolean::Cache,
+    /// Results of offsets.
+    offsets: boolean::Cache,
     mask_elements: Vec<PathEl>,
+    expanded_mask_elements: Vec<PathEl>,
+    stretched_mask_elements: Vec<PathEl>,
 }
 
 impl Renderer {
@@ -106,6 +114,8 @@ impl Renderer {
                 sink,
             );
         }
+        self.merges.evict_unused();
+        self.offsets.evict_unused();
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -139,6 +149,11 @@ impl Renderer {
             self.compute_transform(layer_set, layer, parent_transform, frame);
         let full_rect =
             Rect::new(0.0, 0.0, animation.width as _, animation.height as _);
+        if let Some(blend_mode) = layer.blend_mode {
+            // Isolate the layer so that its content, masks and matte are
+            // composited onto the layers below as a whole.
+            sink.push_layer(blend_mode, 1.0, parent_transform, &full_rect);
+        }
         if let Some((mode, mask_index)) = layer.mask_layer {
             // todo: re-enable masking when it is more understood (and/or if it's currently supported in vello?)
             // Extra layer to isolate blending for the mask
@@ -157,25 +172,28 @@ impl Renderer {
             sink.push_layer(mode, 1.0, parent_transform, &full_rect);
         }
         let alpha = alpha * layer.opacity.evaluate(frame) / 100.0;
-        for mask in &layer.masks {
-            let alpha = mask.opacity.evaluate(frame) / 100.0;
-            mask.geometry.evaluate(frame, &mut self.mask_elements);
-            sink.push_layer(
-                Mix::Clip,
-                alpha,
-                transform,
-                &self.mask_elements.as_slice(),
-            );
-            self.mask_elements.clear();
+        if !layer.masks.is_empty() {
+            // Isolate the content so that the masks apply to it as a whole.
+            sink.push_layer(Mix::Normal, 1.0, parent_transform, &full_rect);
         }
         match &layer.content {
             Content::None => {}
-            Content::Instance {
-                name,
-                time_remap: _,
-            } => {
+            Content::Instance { name, time_remap } => {
                 if let Some(asset_layers) = animation.assets.get(name) {
-                    let frame_delta = -layer.start_frame;
+                    // The time remap is keyed in the time of the layer, which
+                    // starts at the layer start time. Otherwise, the asset
+                    // plays at the stretched rate.
+                    let local_frame = frame - layer.start_frame;
+                    let frame = match time_remap {
+                        Some(time_remap) => {
+                            time_remap.evaluate(local_frame)
+                                * animation.frame_rate
+                        }
+                        None if layer.stretch != 0.0 => {
+                            local_frame / layer.stretch
+                        }
+                        None => local_frame,
+                    };
                     for layer in asset_layers.iter().rev() {
                         if layer.is_mask {
                             continue;
@@ -186,7 +204,7 @@ impl Renderer {
                             layer,
                             transform,
                             alpha,
-                            frame + frame_delta,
+                            frame,
                             sink,
                         );
                     }
@@ -197,15 +215,198 @@ impl Renderer {
                 self.batch.render(sink);
                 self.batch.clear();
             }
+            Content::Solid(color) => {
+                let brush = color.with_alpha_factor(alpha).into();
+                let rect = Rect::new(
+                    0.0,
+                    0.0,
+                    layer.width as f64,
+                    layer.height as f64,
+                );
+                sink.draw(None, Fill::NonZero, transform, &brush, &rect);
+            }
         }
-        for _ in 0..layer.masks.len()
-            + clip as usize
+        if !layer.masks.is_empty() {
+            self.render_masks(
+                &layer.masks,
+                transform,
+                parent_transform,
+                &full_rect,
+                frame,
+                sink,
+            );
+            sink.pop_layer();
+        }
+        for _ in 0..clip as usize
             + (layer.mask_layer.is_some() as usize * 2)
+            + layer.blend_mode.is_some() as usize
         {
             sink.pop_layer();
         }
     }
 
+    /// Multiplies the alpha of the content rendered since the last pushed
+    /// layer by the combined coverage of the masks.
+    fn render_masks(
+        &mut self,
+        masks: &[Mask],
+        transform: Affine,
+        bounds_transform: Affine,
+        bounds: &Rect,
+        frame: f32,
+        sink: &mut impl RenderSink,
+    ) {
+        let compose = |compose| BlendMode::new(Mix::Normal, compose);
+        sink.push_layer(
+            compose(Compose::DestIn),
+            1.0,
+            bounds_transform,
+            bounds,
+        );
+        // Masks that remove coverage start from a fully covered layer.
+        if matches!(
+            masks.first().map(|mask| mask.mode),
+            Some(MaskMode::Subtract | MaskMode::Intersect | MaskMode::Darken)
+        ) {
+            let brush = Color::WHITE.into();
+            sink.draw(None, Fill::NonZero, bounds_transform, &brush, bounds);
+        }
+        for mask in masks {
+            // Lighten, darken and difference are approximated, see
+            // MaskMode.
+            let compose = compose(match mask.mode {
+                MaskMode::Add | MaskMode::Lighten => Compose::SrcOver,
+                MaskMode::Subtract => Compose::DestOut,
+                MaskMode::Intersect | MaskMode::Darken => Compose::DestIn,
+                MaskMode::Difference => Compose::Xor,
+            });
+            let alpha = mask.opacity.evaluate(frame) / 100.0;
+            mask.geometry.evaluate(frame, &mut self.mask_elements);
+            let expansion = mask.expansion.evaluate(frame) as f64;
+            let feather = mask.feather.evaluate(frame);
+            let feather = Vec2::new(feather.x.max(0.0), feather.y.max(0.0));
+            // The mask is drawn in a layer covering the bounds so that
+            // compositing also affects the coverage outside of the mask.
+            sink.push_layer(compose, 1.0, bounds_transform, bounds);
+            if mask.is_inverted {
+                let brush = Color::WHITE.with_alpha_factor(alpha).into();
+                sink.draw(
+                    None,
+                    Fill::NonZero,
+                    bounds_transform,
+                    &brush,
+                    bounds,
+                );
+                sink.push_layer(
+                    BlendMode::new(Mix::Normal, Compose::DestOut),
+                    1.0,
+                    bounds_transform,
+                    bounds,
+                );
+                self.draw_mask(transform, expansion, feather, 1.0, sink);
+                sink.pop_layer();
+            } else {
+                self.draw_mask(transform, expansion, feather, alpha, sink);
+            }
+            sink.pop_layer();
+            self.mask_elements.clear();
+        }
+        sink.pop_layer();
+    }
+
+    /// Draws the evaluated mask geometry grown by `expansion`.
+    ///
+    /// Feathering is approximated by stacking copies of the geometry offset
+    /// across the width of the feather so that the coverage falls off
+    /// linearly from the inside to the outside of the edge. About one copy
+    /// is drawn per device pixel of the wider side of the feather, up to a
+    /// limit. An uneven feather is applied by offsetting the geometry in a
+    /// space stretched so that the feather becomes even.
+    fn draw_mask(
+        &mut self,
+        transform: Affine,
+        expansion: f64,
+        feather: Vec2,
+        alpha: f32,
+        sink: &mut impl RenderSink,
+    ) {
+        const MAX_FEATHER_STEPS: usize = 8;
+        let det = transform.determinant();
+        let width = feather.x.max(feather.y);
+        let steps = ((width * det.abs().sqrt()).ceil() as usize)
+            .clamp(1, MAX_FEATHER_STEPS);
+        let mut elements = &self.mask_elements;
+        let mut expansion = expansion;
+        let mut stretch = Affine::IDENTITY;
+        if feather.x != feather.y && det != 0.0 {
+            // Limits the stretch of a side without feather.
+            const MIN_FEATHER_RATIO: f64 = 1e-3;
+            let min_feather = width * MIN_FEATHER_RATIO;
+            stretch = Affine::scale_non_uniform(
+                width / feather.x.max(min_feather),
+                width / feather.y.max(min_feather),
+            );
+            // The expansion is even, so it is applied before stretching.
+            let unstretched = if expansion != 0.0 {
+                self.expanded_mask_elements.clear();
+                cached_offset(
+                    &mut self.offsets,
+                    &self.mask_elements,
+                    expansion,
+                    Join::Round,
+                    4.0,
+                    flatten_tolerance(transform),
+                    &mut self.expanded_mask_elements,
+                );
+                expansion = 0.0;
+                &self.expanded_mask_elements
+            } else {
+                &self.mask_elements
+            };
+            self.stretched_mask_elements.clear();
+            self.stretched_mask_elements
+                .extend(unstretched.iter().map(|el| stretch * *el));
+            elements = &self.stretched_mask_elements;
+        }
+        let alpha = alpha.clamp(0.0, 1.0);
+        let mut coverage = 0.0;
+        for step in 0..steps {
+            // Copies are drawn from the outermost to the innermost one.
+            let t = (step as f64 + 0.5) / steps as f64;
+            let offset = expansion + width * (0.5 - t);
+            // Each copy raises the coverage inside of it by the same amount
+            // when drawn over the larger copies.
+            let target = alpha * (step + 1) as f32 / steps as f32;
+            let step_alpha = (target - coverage) / (1.0 - coverage);
+            coverage = target;
+            let shape = if offset != 0.0 && det != 0.0 {
+                self.expanded_mask_elements.clear();
+                // Stretching only scales up, so the tolerance is kept.
+                cached_offset(
+                    &mut self.offsets,
+                    elements,
+                    offset,
+                    Join::Round,
+                    4.0,
+                    flatten_tolerance(transform),
+                    &mut self.expanded_mask_elements,
+                );
+                &self.expanded_mask_elements
+            } else {
+                elements
+            };
+            let brush = Color::WHITE.with_alpha_factor(step_alpha).into();
+            sink.draw(
+                None,
+                Fill::NonZero,
+                transform * stretch.inverse(),
+                &brush,
+                &shape.as_slice(),
+            );
+        }
+        self.expanded_mask_elements.clear();
+    }
+
     fn render_shapes(
         &mut self,
         shapes: &[Shape],
@@ -218,29 +419,47 @@ impl Renderer {
         let geometry_start = self.batch.geometries.len();
         // Also keep track of top of draw stack for repeater evaluation.
         let draw_start = self.batch.draws.len();
+        // Paths are operands of a merge or trimmed simultaneously, so keep
+        // them apart.
+        let keeps_paths_apart = shapes.iter().any(|shape| match shape {
+            Shape::Merge(mode) => *mode != MergeMode::Merge,
+            Shape::Trim(trim) => !trim.is_individual(),
+            _ => false,
+        });
         // Top to bottom, collect geometries and draws.
         for shape in shapes {
             match shape {
                 Shape::Group(shapes, group_transform) => {
-                    let (group_transform, group_alpha) =
-                        if let Some(GroupTransform { transform, opacity }) =
-                            group_transform
+                    let (group_transform, group_alpha, blend_mode) =
+                        if let Some(GroupTransform {
+                            transform,
+                            opacity,
+                            blend_mode,
+                        }) = group_transform
                         {
                             (
                                 transform.evaluate(frame).to_owned(),
                                 opacity.evaluate(frame) / 100.0,
+                                *blend_mode,
                             )
                         } else {
-                            (Affine::IDENTITY, 1.0)
+                            (Affine::IDENTITY, 1.0, None)
                         };
+                    let group_draw_start = self.batch.draws.len();
                     self.render_shapes(
                         shapes,
                         transform * group_transform,
                         alpha * group_alpha,
                         frame,
                     );
+                    if let Some(blend_mode) = blend_mode {
+                        self.batch.push_group(blend_mode, group_draw_start);
+                    }
                 }
                 Shape::Geometry(geometry) => {
+                    if keeps_paths_apart {
+                        self.batch.drawn_geometry = self.batch.geometries.len();
+                    }
                     self.batch.push_geometry(geometry, transform, frame);
                 }
                 Shape::Draw(draw) => {
@@ -254,6 +473,47 @@ impl Renderer {
                         draw_start,
                     );
                 }
+                Shape::Trim(trim) => {
+                    let trim = trim.evaluate(frame);
+                    self.batch.trim(trim.as_ref(), geometry_start);
+                }
+                Shape::Merge(mode) => {
+                    self.batch.merge(
+                        *mode,
+                        transform,
+                        geometry_start,
+                        &mut self.merges,
+                    );
+                }
+                Shape::PuckerBloat(amount) => {
+                    let amount = amount.evaluate(frame);
+                    self.batch.pucker_bloat(amount, geometry_start);
+                }
+                Shape::ZigZag(zig_zag) => {
+                    let zig_zag = zig_zag.evaluate(frame);
+                    self.batch.zig_zag(
+                        zig_zag.as_ref(),
+                        transform,
+                        geometry_start,
+                    );
+                }
+                Shape::Twist(twist) => {
+                    let twist = twist.evaluate(frame);
+                    self.batch.twist(twist.as_ref(), transform, geometry_start);
+                }
+                Shape::RoundedCorners(radius) => {
+                    let radius = radius.evaluate(frame);
+                    self.batch.round_corners(radius, transform, geometry_start);
+                }
+                Shape::OffsetPath(offset) => {
+                    let offset = offset.evaluate(frame);
+                    self.batch.offset(
+                        offset.as_ref(),
+                        transform,
+                        geometry_start,
+                        &mut self.offsets,
+                    );
+                }
             }
         }
     }
@@ -268,7 +528,7 @@ impl Renderer {
         global_transform: Affine,
         frame: f32,
     ) -> Affine {
-        let mut transform = layer.transform.evaluate(frame).to_owned();
+        let mut transform = Self::layer_transform(layer, frame);
         let mut parent_index = layer.parent;
         let mut count = 0usize;
         while let Some(index) = parent_index {
@@ -279,8 +539,7 @@ impl Renderer {
             }
             if let Some(parent) = layer_set.get(index) {
                 parent_index = parent.parent;
-                transform =
-                    parent.transform.evaluate(frame).to_owned() * transform;
+                transform = Self::layer_transform(parent, frame) * transform;
                 count += 1;
             } else {
                 break;
@@ -288,13 +547,24 @@ impl Renderer {
         }
         global_transform * transform
     }
+
+    /// Evaluates the local transform of a layer, including auto-orientation.
+    fn layer_transform(layer: &Layer, frame: f32) -> Affine {
+        if layer.auto_orient {
+            layer.transform.evaluate_auto_oriented(frame).to_owned()
+        } else {
+            layer.transform.evaluate(frame).to_owned()
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
 struct DrawData {
     stroke: Option<fixed::Stroke>,
+    fill_rule: Fill,
     brush: fixed::Brush,
     alpha: f32,
+    blend_mode: Option<peniko::BlendMode>,
     /// Range into ShapeBatch::geometries
     geometry: Range<usize>,
 }
@@ -311,13 +581,22 @@ impl DrawData {
                 .stroke
                 .as_ref()
                 .map(|stroke| stroke.evaluate(frame).to_owned()),
+            fill_rule: draw.fill_rule,
             brush: draw.brush.evaluate(1.0, frame).to_owned(),
             alpha: alpha * draw.opacity.evaluate(frame) / 100.0,
+            blend_mode: draw.blend_mode,
             geometry,
         }
     }
 }
 
+#[derive(Clone, Debug)]
+struct GroupData {
+    blend_mode: peniko::BlendMode,
+    /// Range into ShapeBatch::draws
+    draws: Range<usize>,
+}
+
 #[derive(Clone, Debug)]
 struct GeometryData {
     /// Range into ShapeBatch::elements
@@ -330,8 +609,16 @@ struct Batch {
     elements: Vec<PathEl>,
     geometries: Vec<GeometryData>,
     draws: Vec<DrawData>,
+    /// Groups with a blend mode, each composited as a single unit. Nested
+    /// groups precede the groups containing them.
+    groups: Vec<GroupData>,
     repeat_geometries: Vec<GeometryData>,
     repeat_draws: Vec<DrawData>,
+    repeat_groups: Vec<GroupData>,
+    /// Scratch buffer for geometry modified in place.
+    modified_elements: Vec<PathEl>,
+    /// Ranges into modified_elements for the operands of a merge.
+    operands: Vec<Range<usize>>,
     /// Length of geometries at time of most recent draw. This is
     /// used to prevent merging into already used geometries.
     drawn_geometry: usize,
@@ -381,6 +668,15 @@ impl Batch {
         self.drawn_geometry = self.geometries.len();
     }
 
+    fn push_group(&mut self, blend_mode: peniko::BlendMode, draw_start: usize) {
+        if draw_start < self.draws.len() {
+            self.groups.push(GroupData {
+                blend_mode,
+                draws: draw_start..self.draws.len(),
+            });
+        }
+    }
+
     fn repeat(
         &mut self,
         repeater: &fixed::Repeater,
@@ -391,10 +687,17 @@ impl Batch {
         self.repeat_geometries
             .extend(self.geometries.drain(geometry_start..));
         self.repeat_draws.extend(self.draws.drain(draw_start..));
-        // Next, repeat the geometries and apply the offset transform
-        for geometry in self.repeat_geometries.iter() {
-            for i in 0..repeater.copies {
-                let transform = repeater.transform(i);
+        let group_start = self
+            .groups
+            .iter()
+            .position(|group| group.draws.start >= draw_start)
+            .unwrap_or(self.groups.len());
+        self.repeat_groups.extend(self.groups.drain(group_start..));
+        // Next, repeat the geometries and apply the offset transform. The
+        // geometries of each copy are contiguous.
+        for i in 0..repeater.copies {
+            let transform = repeater.transform(i);
+            for geometry in self.repeat_geometries.iter() {
                 let mut geometry = geometry.clone();
                 geometry.transform *= transform;
                 self.geometries.push(geometry);
@@ -410,32 +713,319 @@ impl Batch {
         } else {
             0.0
         };
-        for i in 0..repeater.copies {
+        let copy_len = self.repeat_geometries.len();
+        // Earlier draws are rendered on top, so copies stacked above the
+        // previous ones are pushed in reverse.
+        let order = (0..repeater.copies).map(|i| {
+            if repeater.is_above {
+                repeater.copies - 1 - i
+            } else {
+                i
+            }
+        });
+        for i in order {
             let alpha = start_alpha + delta_alpha * i as f32;
             if alpha <= 0.0 {
                 continue;
             }
+            let copy_start = geometry_start + i as usize * copy_len;
+            let copy_draw_start = self.draws.len();
             for mut draw in self.repeat_draws.iter().cloned() {
                 draw.alpha *= alpha;
                 let count = draw.geometry.end - draw.geometry.start;
-                draw.geometry.start = geometry_start
-                    + (draw.geometry.start - geometry_start)
-                        * repeater.copies as usize;
-                draw.geometry.end =
-                    draw.geometry.start + count * repeater.copies as usize;
+                draw.geometry.start =
+                    copy_start + (draw.geometry.start - geometry_start);
+                draw.geometry.end = draw.geometry.start + count;
                 self.draws.push(draw);
             }
+            for mut group in self.repeat_groups.iter().cloned() {
+                let count = group.draws.end - group.draws.start;
+                group.draws.start =
+                    copy_draw_start + (group.draws.start - draw_start);
+                group.draws.end = group.draws.start + count;
+                self.groups.push(group);
+            }
         }
         // Clear the side buffers
         self.repeat_geometries.clear();
         self.repeat_draws.clear();
+        self.repeat_groups.clear();
         // Prevent merging until new geometries are pushed
         self.drawn_geometry = self.geometries.len();
     }
 
+    fn trim(&mut self, trim: &fixed::Trim, geometry_start: usize) {
+        // When trimming individually, all subpaths form a single path
+        // measured in order. Otherwise, the subpaths of each shape are
+        // measured in order on their own.
+        let mut total_length = if trim.is_individual {
+            self.geometries[geometry_start..]
+                .iter()
+                .flat_map(|geometry| {
+                    path::subpaths(&self.elements[geometry.elements.clone()])
+                })
+                .map(path::length)
+                .sum()
+        } else {
+            0.0
+        };
+        let mut offset = 0.0;
+        for geometry in self.geometries[geometry_start..].iter_mut() {
+            let elements = &self.elements[geometry.elements.clone()];
+            if !trim.is_individual {
+                total_length = path::subpaths(elements).map(path::length).sum();
+                offset = 0.0;
+            }
+            for subpath in path::subpaths(elements) {
+                let length = path::length(subpath);
+                for range in trim.ranges() {
+                    path::trim(
+                        subpath,
+                        length,
+                        range.start * total_length - offset
+                            ..range.end * total_length - offset,
+                        &mut self.modified_elements,
+                    );
+                }
+                offset += length;
+            }
+            // Trimmed elements are appended and the original range is left
+            // unused.
+            let start = self.elements.len();
+            self.elements.append(&mut self.modified_elements);
+            geometry.elements = start..self.elements.len();
+        }
+        // Prevent merging into trimmed geometry
+        self.drawn_geometry = self.geometries.len();
+    }
+
+    fn pucker_bloat(&mut self, amount: f32, geometry_start: usize) {
+        if amount == 0.0 {
+            return;
+        }
+        // Moving points toward a center commutes with affine transforms, so
+        // each geometry is modified in its own space.
+        for geometry in self.geometries[geometry_start..].iter_mut() {
+            let elements = &self.elements[geometry.elements.clone()];
+            for subpath in path::subpaths(elements) {
+                path::pucker_bloat(
+                    subpath,
+                    amount as f64 / 100.0,
+                    &mut self.modified_elements,
+                );
+            }
+            let start = self.elements.len();
+            self.elements.append(&mut self.modified_elements);
+            geometry.elements = start..self.elements.len();
+        }
+        // Prevent merging into modified geometry
+        self.drawn_geometry = self.geometries.len();
+    }
+
+    fn merge(
+        &mut self,
+        mode: MergeMode,
+        transform: Affine,
+        geometry_start: usize,
+        cache: &mut boolean::Cache,
+    ) {
+        let inside = match mode {
+            MergeMode::Merge => return,
+            MergeMode::Add => |flags: &[bool]| flags.iter().any(|f| *f),
+            MergeMode::Subtract => |flags: &[bool]| {
+                flags.first() == Some(&true) && !flags[1..].iter().any(|f| *f)
+            },
+            MergeMode::Intersect => |flags: &[bool]| flags.iter().all(|f| *f),
+            MergeMode::ExcludeIntersections => {
+                |flags: &[bool]| flags.iter().filter(|f| **f).count() % 2 == 1
+            }
+        };
+        // Combine in the local space of the merge. Geometries from nested
+        // groups are mapped into it.
+        if geometry_start >= self.geometries.len()
+            || transform.determinant() == 0.0
+        {
+            return;
+        }
+        self.collect_local(transform, geometry_start);
+        // Geometry emptied by a previous modifier is not an operand.
+        let operands = self
+            .operands
+            .iter()
+            .filter(|range| !range.is_empty())
+            .map(|range| &self.modified_elements[range.clone()])
+            .collect::<Vec<_>>();
+        let start = self.elements.len();
+        let tolerance = flatten_tolerance(transform);
+        cache.get_or_compute(
+            &[mode as u8 as f64, tolerance],
+            &operands,
+            &mut self.elements,
+            |out| boolean::combine(&operands, tolerance, inside, out),
+        );
+        let end = self.elements.len();
+        // The result replaces the first geometry. The others are kept empty
+        // so that ranges referenced by draws remain valid.
+        for (i, geometry) in
+            self.geometries[geometry_start..].iter_mut().enumerate()
+        {
+            if i == 0 {
+                geometry.elements = start..end;
+                geometry.transform = transform;
+            } else {
+                geometry.elements = end..end;
+            }
+        }
+        self.operands.clear();
+        self.modified_elements.clear();
+        // Prevent merging into the result
+        self.drawn_geometry = self.geometries.len();
+    }
+
+    fn offset(
+        &mut self,
+        offset: &fixed::OffsetPath,
+        transform: Affine,
+        geometry_start: usize,
+        cache: &mut boolean::Cache,
+    ) {
+        if offset.amount == 0.0 || transform.determinant() == 0.0 {
+            return;
+        }
+        let tolerance = flatten_tolerance(transform);
+        self.modify_local(transform, geometry_start, |elements, out| {
+            cached_offset(
+                cache,
+                elements,
+                offset.amount as f64,
+                offset.join,
+                offset.miter_limit as f64,
+                tolerance,
+                out,
+            );
+        });
+    }
+
+    fn zig_zag(
+        &mut self,
+        zig_zag: &fixed::ZigZag,
+        transform: Affine,
+        geometry_start: usize,
+    ) {
+        let frequency = zig_zag.frequency.max(0.0) as usize;
+        let amplitude = zig_zag.amplitude as f64;
+        if amplitude == 0.0 && frequency == 0 {
+            return;
+        }
+        self.modify_local(transform, geometry_start, |elements, out| {
+            for subpath in path::subpaths(elements) {
+                path::zig_zag(
+                    subpath,
+                    frequency,
+                    amplitude,
+                    zig_zag.is_smooth,
+                    out,
+                );
+            }
+        });
+    }
+
+    fn twist(
+        &mut self,
+        twist: &fixed::Twist,
+        transform: Affine,
+        geometry_start: usize,
+    ) {
+        if twist.angle == 0.0 {
+            return;
+        }
+        let angle = (twist.angle as f64).to_radians();
+        self.modify_local(transform, geometry_start, |elements, out| {
+            path::twist(elements, angle, twist.center, out);
+        });
+    }
+
+    fn round_corners(
+        &mut self,
+        radius: f32,
+        transform: Affine,
+        geometry_start: usize,
+    ) {
+        if radius <= 0.0 {
+            return;
+        }
+        self.modify_local(transform, geometry_start, |elements, out| {
+            for subpath in path::subpaths(elements) {
+                path::round_corners(subpath, radius as f64, out);
+            }
+        });
+    }
+
+    /// Replaces the paths of the geometries from `geometry_start` with the
+    /// output of `modify`, which receives each path in the space of the
+    /// modifier.
+    fn modify_local(
+        &mut self,
+        transform: Affine,
+        geometry_start: usize,
+        mut modify: impl FnMut(&[PathEl], &mut Vec<PathEl>),
+    ) {
+        if transform.determinant() == 0.0 {
+            return;
+        }
+        self.collect_local(transform, geometry_start);
+        let geometries = self.geometries[geometry_start..].iter_mut();
+        for (geometry, range) in geometries.zip(&self.operands) {
+            // Geometry emptied by a previous modifier is left as it is.
+            if range.is_empty() {
+                continue;
+            }
+            let start = self.elements.len();
+            modify(&self.modified_elements[range.clone()], &mut self.elements);
+            geometry.elements = start..self.elements.len();
+            geometry.transform = transform;
+        }
+        self.operands.clear();
+        self.modified_elements.clear();
+        // Prevent merging into modified geometry
+        self.drawn_geometry = self.geometries.len();
+    }
+
+    /// Appends the paths of the geometries from `geometry_start` to
+    /// `modified_elements`, mapped into the space of a modifier with the
+    /// given invertible transform, and their ranges to `operands`.
+    fn collect_local(&mut self, transform: Affine, geometry_start: usize) {
+        let inverse = transform.inverse();
+        for geometry in &self.geometries[geometry_start..] {
+            let start = self.modified_elements.len();
+            let elements = &self.elements[geometry.elements.clone()];
+            // Geometry in the space of the modifier is kept exact so that
+            // results may be cached.
+            if geometry.transform == transform {
+                self.modified_elements.extend_from_slice(elements);
+            } else {
+                let local = inverse * geometry.transform;
+                self.modified_elements
+                    .extend(elements.iter().map(|el| local * *el));
+            }
+            self.operands.push(start..self.modified_elements.len());
+        }
+    }
+
     fn render(&self, sink: &mut impl RenderSink) {
         // Process all draws in reverse
-        for draw in self.draws.iter().rev() {
+        for (i, draw) in self.draws.iter().enumerate().rev() {
+            // Groups ending at this draw are entered, outermost first.
+            for group in self.groups.iter().rev() {
+                if group.draws.end == i + 1 {
+                    sink.push_layer(
+                        group.blend_mode,
+                        1.0,
+                        Affine::IDENTITY,
+                        &self.group_bounds(group),
+                    );
+                }
+            }
             // Some nastiness to avoid cloning the brush if unnecessary
             let modified_brush = if draw.alpha != 1.0 {
                 Some(fixed::brush_with_alpha(&draw.brush, draw.alpha))
@@ -443,20 +1033,121 @@ impl Batch {
                 None
             };
             let brush = modified_brush.as_ref().unwrap_or(&draw.brush);
+            if let Some(blend_mode) = draw.blend_mode {
+                sink.push_layer(
+                    blend_mode,
+                    1.0,
+                    Affine::IDENTITY,
+                    &self.draw_bounds(draw),
+                );
+            }
+            // Dashes are cut out of the paths here so that the sink only
+            // receives solid strokes.
+            let dashes = draw
+                .stroke
+                .as_ref()
+                .filter(|stroke| !stroke.dash_pattern.is_empty());
+            let solid_stroke = dashes.map(|stroke| {
+                let mut stroke = stroke.clone();
+                stroke.dash_pattern.clear();
+                stroke
+            });
+            let stroke = solid_stroke.as_ref().or(draw.stroke.as_ref());
             for geometry in self.geometries[draw.geometry.clone()].iter() {
-                let path = &self.elements[geometry.elements.clone()];
+                let mut path = &self.elements[geometry.elements.clone()];
+                let dashed;
+                if let Some(dashes) = dashes {
+                    dashed = kurbo::dash(
+                        path.iter().copied(),
+                        dashes.dash_offset,
+                        &dashes.dash_pattern,
+                    )
+                    .collect::<Vec<_>>();
+                    path = &dashed;
+                }
+                if path.is_empty() {
+                    continue;
+                }
                 let transform = geometry.transform;
-                sink.draw(draw.stroke.as_ref(), transform, brush, &path);
+                sink.draw(stroke, draw.fill_rule, transform, brush, &path);
+            }
+            if draw.blend_mode.is_some() {
+                sink.pop_layer();
+            }
+            for group in &self.groups {
+                if group.draws.start == i {
+                    sink.pop_layer();
+                }
             }
         }
     }
 
+    /// Computes the device space bounds of all draws in a group.
+    fn group_bounds(&self, group: &GroupData) -> Rect {
+        self.draws[group.draws.clone()]
+            .iter()
+            .map(|draw| self.draw_bounds(draw))
+            .reduce(|a, b| a.union(b))
+            .unwrap_or_default()
+    }
+
+    /// Computes the device space bounds of all geometries referenced by a
+    /// draw, including the area covered by the stroke.
+    fn draw_bounds(&self, draw: &DrawData) -> Rect {
+        let padding = draw.stroke.as_ref().map_or(0.0, |stroke| {
+            if stroke.join == Join::Miter {
+                stroke.width * stroke.miter_limit.max(1.0)
+            } else {
+                stroke.width
+            }
+        });
+        self.geometries[draw.geometry.clone()]
+            .iter()
+            .map(|geometry| {
+                let path = &self.elements[geometry.elements.clone()];
+                let bounds = path.bounding_box().inflate(padding, padding);
+                geometry.transform.transform_rect_bbox(bounds)
+            })
+            .reduce(|a, b| a.union(b))
+            .unwrap_or_default()
+    }
+
     fn clear(&mut self) {
         self.elements.clear();
         self.geometries.clear();
         self.draws.clear();
+        self.groups.clear();
         self.repeat_geometries.clear();
         self.repeat_draws.clear();
+        self.repeat_groups.clear();
+        self.modified_elements.clear();
+        self.operands.clear();
         self.drawn_geometry = 0;
     }
 }
+
+/// Returns the tolerance for flattening curves drawn with the transform,
+/// matching the tolerance used for shapes in device space. It is rounded
+/// down to a power of two so that it does not change between frames when
+/// the scale of the transform is animated slightly or not at all.
+fn flatten_tolerance(transform: Affine) -> f64 {
+    let tolerance = 0.1 / transform.determinant().abs().sqrt();
+    tolerance.log2().floor().exp2()
+}
+
+/// Appends the outline of a path grown by `amount`, reusing the result of a
+/// previous call with the same inputs.
+fn cached_offset(
+    cache: &mut boolean::Cache,
+    elements: &[PathEl],
+    amount: f64,
+    join: Join,
+    miter_limit: f64,
+    tolerance: f64,
+    out: &mut Vec<PathEl>,
+) {
+    let params = [amount, join as u8 as f64, miter_limit, tolerance];
+    cache.get_or_compute(&params, &[elements], out, |out| {
+        path::offset(elements, amount, join, miter_limit, tolerance, out);
+    });
+}
@@ -0,0 +1,127 @@
+use serde_json::json;
+use vellottie::runtime::{
+    model::fixed,
+    vello::{
+        kurbo::{Affine, Point, Shape},
+        peniko::{BlendMode, Fill, GradientKind},
+    },
+    Composition, RenderSink, Renderer,
+};
+
+/// Render sink that records the kind of each gradient brush.
+#[derive(Default)]
+struct Gradients(Vec<GradientKind>);
+
+impl RenderSink for Gradients {
+    fn push_layer(
+        &mut self,
+        _blend: impl Into<BlendMode>,
+        _alpha: f32,
+        _transform: Affine,
+        _shape: &impl Shape,
+    ) {
+    }
+
+    fn pop_layer(&mut self) {}
+
+    fn draw(
+        &mut self,
+        _stroke: Option<&fixed::Stroke>,
+        _fill: Fill,
+        _transform: Affine,
+        brush: &fixed::Brush,
+        _shape: &impl Shape,
+    ) {
+        let fixed::Brush::Gradient(gradient) = brush else {
+            panic!("expected a gradient");
+        };
+        self.0.push(gradient.kind);
+    }
+}
+
+/// Start center and radius, and end center and radius of a square filled
+/// with a radial gradient from (0, 0) to (10, 0) and the given highlight
+/// length and angle.
+fn radial_gradient(highlight: Option<(f32, f32)>) -> (Point, f32, Point, f32) {
+    let static_value = |value| json!({ "a": 0, "k": value });
+    let mut gradient = json!({
+        "ty": "gf",
+        "o": static_value(json!(100)),
+        "g": {
+            "p": 2,
+            "k": static_value(json!([0, 1, 0, 0, 1, 0, 0, 1]))
+        },
+        "s": static_value(json!([0, 0])),
+        "e": static_value(json!([10, 0])),
+        "t": 2
+    });
+    if let Some((length, angle)) = highlight {
+        gradient["h"] = static_value(json!(length));
+        gradient["a"] = static_value(json!(angle));
+    }
+    let lottie = json!({
+        "v": "5.5.2",
+        "fr": 10,
+        "ip": 0,
+        "op": 20,
+        "w": 100,
+        "h": 100,
+        "layers": [{
+            "ty": 4,
+            "ind": 1,
+            "st": 0,
+            "ip": 0,
+            "op": 20,
+            "ks": {
+                "a": static_value(json!([0, 0])),
+                "p": static_value(json!([0, 0])),
+                "s": static_value(json!([100, 100])),
+                "r": static_value(json!(0)),
+                "o": static_value(json!(100))
+            },
+            "shapes": [
+                {
+                    "ty": "rc",
+                    "p": static_value(json!([5, 5])),
+                    "s": static_value(json!([10, 10])),
+                    "r": static_value(json!(0))
+                },
+                gradient
+            ]
+        }]
+    });
+    let composition =
+        Composition::from_bytes(serde_json::to_vec(&lottie).unwrap()).unwrap();
+    let mut sink = Gradients::default();
+    Renderer::new().render_frame(
+        &composition,
+        0.0,
+        Affine::IDENTITY,
+        1.0,
+        &mut sink,
+    );
+    match sink.0[..] {
+        [GradientKind::Radial {
+            start_center,
+            start_radius,
+            end_center,
+            end_radius,
+        }] => (start_center, start_radius, end_center, end_radius),
+        _ => panic!("expected a single radial gradient"),
+    }
+}
+
+#[test]
+fn test_radial_gradient_highlight() {
+    // Without a highlight, the gradient is centered on the start point.
+    let (focus, start_radius, center, radius) = radial_gradient(None);
+    assert_eq!((focus, start_radius), ((0.0, 0.0).into(), 0.0));
+    assert_eq!((center, radius), ((0.0, 0.0).into(), 10.0));
+    // The highlight is rotated from the direction of the end point.
+    let (focus, _, center, radius) = radial_gradient(Some((50.0, 90.0)));
+    assert!((focus - Point::new(0.0, 5.0)).hypot() < 1e-9);
+    assert_eq!((center, radius), ((0.0, 0.0).into(), 10.0));
+    // It stays inside the end circle.
+    let (focus, _, _, _) = radial_gradient(Some((100.0, 0.0)));
+    assert!((focus - Point::new(9.9, 0.0)).hypot() < 1e-9);
+}
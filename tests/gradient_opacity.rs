@@ -0,0 +1,145 @@
+use serde_json::json;
+use vellottie::runtime::{
+    model::fixed,
+    vello::{
+        kurbo::{Affine, Shape},
+        peniko::{BlendMode, Fill},
+    },
+    Composition, RenderSink, Renderer,
+};
+
+/// Render sink that records the stop alphas and fill rule of each gradient
+/// draw.
+#[derive(Default)]
+struct Gradients(Vec<(Vec<u8>, Fill)>);
+
+impl RenderSink for Gradients {
+    fn push_layer(
+        &mut self,
+        _blend: impl Into<BlendMode>,
+        _alpha: f32,
+        _transform: Affine,
+        _shape: &impl Shape,
+    ) {
+    }
+
+    fn pop_layer(&mut self) {}
+
+    fn draw(
+        &mut self,
+        _stroke: Option<&fixed::Stroke>,
+        fill: Fill,
+        _transform: Affine,
+        brush: &fixed::Brush,
+        _shape: &impl Shape,
+    ) {
+        let fixed::Brush::Gradient(gradient) = brush else {
+            panic!("expected a gradient");
+        };
+        let alphas = gradient.stops.iter().map(|stop| stop.color.a);
+        self.0.push((alphas.collect(), fill));
+    }
+}
+
+fn static_value(value: serde_json::Value) -> serde_json::Value {
+    json!({ "a": 0, "k": value })
+}
+
+/// Stop alphas and fill rule of a square drawn with `gradient` at `frame`.
+fn render_gradient(gradient: serde_json::Value, frame: f32) -> (Vec<u8>, Fill) {
+    let lottie = json!({
+        "v": "5.5.2",
+        "fr": 10,
+        "ip": 0,
+        "op": 20,
+        "w": 100,
+        "h": 100,
+        "layers": [{
+            "ty": 4,
+            "ind": 1,
+            "st": 0,
+            "ip": 0,
+            "op": 20,
+            "ks": {
+                "a": static_value(json!([0, 0])),
+                "p": static_value(json!([0, 0])),
+                "s": static_value(json!([100, 100])),
+                "r": static_value(json!(0)),
+                "o": static_value(json!(100))
+            },
+            "shapes": [
+                {
+                    "ty": "rc",
+                    "p": static_value(json!([5, 5])),
+                    "s": static_value(json!([10, 10])),
+                    "r": static_value(json!(0))
+                },
+                gradient
+            ]
+        }]
+    });
+    let composition =
+        Composition::from_bytes(serde_json::to_vec(&lottie).unwrap()).unwrap();
+    let mut sink = Gradients::default();
+    Renderer::new().render_frame(
+        &composition,
+        frame,
+        Affine::IDENTITY,
+        1.0,
+        &mut sink,
+    );
+    match sink.0.pop() {
+        Some(gradient) if sink.0.is_empty() => gradient,
+        _ => panic!("expected a single gradient draw"),
+    }
+}
+
+/// Opaque white to opaque white.
+fn colors() -> serde_json::Value {
+    json!({
+        "p": 2,
+        "k": static_value(json!([0, 1, 0, 0, 1, 0, 0, 1]))
+    })
+}
+
+#[test]
+fn test_gradient_fill_opacity() {
+    let fill = json!({
+        "ty": "gf",
+        "o": {
+            "a": 1,
+            "k": [
+                { "t": 0, "s": [100], "i": { "x": [1], "y": [1] }, "o": { "x": [0], "y": [0] } },
+                { "t": 10, "s": [0] }
+            ]
+        },
+        "r": 2,
+        "g": colors(),
+        "s": static_value(json!([0, 0])),
+        "e": static_value(json!([10, 0])),
+        "t": 1
+    });
+    assert_eq!(
+        render_gradient(fill.clone(), 0.0),
+        (vec![255, 255], Fill::EvenOdd)
+    );
+    let (alphas, _) = render_gradient(fill, 5.0);
+    assert!(alphas.iter().all(|alpha| alpha.abs_diff(128) <= 1));
+}
+
+#[test]
+fn test_gradient_stroke_opacity() {
+    let stroke = json!({
+        "ty": "gs",
+        "o": static_value(json!(50)),
+        "w": static_value(json!(2)),
+        "lc": 1,
+        "lj": 1,
+        "g": colors(),
+        "s": static_value(json!([0, 0])),
+        "e": static_value(json!([10, 0])),
+        "t": 1
+    });
+    let (alphas, _) = render_gradient(stroke, 0.0);
+    assert!(alphas.iter().all(|alpha| alpha.abs_diff(128) <= 1));
+}
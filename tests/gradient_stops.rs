@@ -0,0 +1,147 @@
+use serde_json::json;
+use vellottie::runtime::{
+    model::{fixed, Brush, Content, Shape},
+    vello::peniko::{Color, ColorStop},
+    Composition,
+};
+
+fn stops(stops: &[ColorStop]) -> Vec<(f32, [u8; 4])> {
+    stops
+        .iter()
+        .map(|stop| {
+            let Color { r, g, b, a } = stop.color;
+            (stop.offset, [r, g, b, a])
+        })
+        .collect()
+}
+
+#[test]
+fn test_color_stops_without_opacity() {
+    let values = [0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0];
+    assert_eq!(
+        stops(&fixed::color_stops(&values, 2)),
+        [(0.0, [255, 0, 0, 255]), (1.0, [0, 0, 255, 255])]
+    );
+}
+
+#[test]
+fn test_color_stops_union() {
+    // Red to blue, opaque at the ends and transparent in the middle.
+    let values = [
+        0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, //
+        0.0, 1.0, 0.5, 0.0, 1.0, 1.0,
+    ];
+    assert_eq!(
+        stops(&fixed::color_stops(&values, 2)),
+        [
+            (0.0, [255, 0, 0, 255]),
+            (0.5, [128, 0, 128, 0]),
+            (1.0, [0, 0, 255, 255])
+        ]
+    );
+    // Opacity stops inside the color stops extend to the ends.
+    let values = [
+        0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, //
+        0.25, 0.5, 0.75, 1.0,
+    ];
+    assert_eq!(
+        stops(&fixed::color_stops(&values, 2)),
+        [
+            (0.0, [255, 0, 0, 128]),
+            (0.25, [191, 0, 64, 128]),
+            (0.75, [64, 0, 191, 255]),
+            (1.0, [0, 0, 255, 255])
+        ]
+    );
+}
+
+#[test]
+fn test_color_stops_hard_edge() {
+    // Color stops sharing an offset are kept.
+    let values = [
+        0.0, 1.0, 0.0, 0.0, 0.5, 1.0, 0.0, 0.0, 0.5, 0.0, 0.0, 1.0, //
+        0.0, 1.0, 1.0, 0.0,
+    ];
+    assert_eq!(
+        stops(&fixed::color_stops(&values, 3)),
+        [
+            (0.0, [255, 0, 0, 255]),
+            (0.5, [255, 0, 0, 128]),
+            (0.5, [0, 0, 255, 128]),
+            (1.0, [0, 0, 255, 0])
+        ]
+    );
+}
+
+#[test]
+fn test_animated_color_stops() {
+    let keyframe = |t: f32, alpha_offset: f32| {
+        json!({
+            "t": t,
+            "s": [0, 1, 0, 0, 1, 0, 0, 1, alpha_offset, 0],
+            "i": { "x": [1], "y": [1] },
+            "o": { "x": [0], "y": [0] }
+        })
+    };
+    let lottie = json!({
+        "v": "5.5.2",
+        "fr": 10,
+        "ip": 0,
+        "op": 20,
+        "w": 100,
+        "h": 100,
+        "layers": [{
+            "ty": 4,
+            "ind": 1,
+            "st": 0,
+            "ip": 0,
+            "op": 20,
+            "ks": {
+                "a": { "a": 0, "k": [0, 0] },
+                "p": { "a": 0, "k": [0, 0] },
+                "s": { "a": 0, "k": [100, 100] },
+                "r": { "a": 0, "k": 0 },
+                "o": { "a": 0, "k": 100 }
+            },
+            "shapes": [{
+                "ty": "gf",
+                "o": { "a": 0, "k": 100 },
+                "g": {
+                    "p": 2,
+                    "k": { "a": 1, "k": [keyframe(0.0, 0.0), keyframe(10.0, 1.0)] }
+                },
+                "s": { "a": 0, "k": [0, 0] },
+                "e": { "a": 0, "k": [10, 0] },
+                "t": 1
+            }]
+        }]
+    });
+    let composition =
+        Composition::from_bytes(serde_json::to_vec(&lottie).unwrap()).unwrap();
+    let Content::Shape(shapes) = &composition.layers[0].content else {
+        panic!("expected shape layer");
+    };
+    let Some(Shape::Draw(draw)) = shapes.first() else {
+        panic!("expected gradient fill");
+    };
+    let gradient = |frame: f32| match draw.brush.evaluate(1.0, frame).to_owned()
+    {
+        fixed::Brush::Gradient(gradient) => stops(&gradient.stops),
+        _ => panic!("expected gradient brush"),
+    };
+    // The opacity stop moves across the gradient, adding a stop between the
+    // color stops halfway through.
+    assert_eq!(
+        gradient(0.0),
+        [(0.0, [255, 0, 0, 0]), (1.0, [0, 0, 255, 0])]
+    );
+    assert_eq!(
+        gradient(5.0),
+        [
+            (0.0, [255, 0, 0, 0]),
+            (0.5, [128, 0, 128, 0]),
+            (1.0, [0, 0, 255, 0])
+        ]
+    );
+    assert!(matches!(draw.brush, Brush::Animated(_)));
+}
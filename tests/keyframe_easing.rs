@@ -0,0 +1,76 @@
+use vellottie::runtime::model::{Animated, Easing, Time};
+use vellottie::runtime::vello::kurbo::Point;
+
+fn animated(easing: Option<Easing>) -> Animated<f32> {
+    Animated {
+        times: vec![
+            Time {
+                frame: 0.0,
+                hold: false,
+                easing,
+                component_easing: None,
+            },
+            Time {
+                frame: 10.0,
+                hold: false,
+                easing: None,
+                component_easing: None,
+            },
+        ],
+        values: vec![0.0, 100.0],
+    }
+}
+
+#[test]
+fn test_linear_easing() {
+    let linear = animated(None);
+    let eased = animated(Some(Easing::LINEAR));
+    for frame in 0..=10 {
+        let frame = frame as f32;
+        assert!((linear.evaluate(frame) - frame * 10.0).abs() < 1e-3);
+        assert!((eased.evaluate(frame) - frame * 10.0).abs() < 1e-3);
+    }
+}
+
+#[test]
+fn test_ease_in_out() {
+    let eased = animated(Some(Easing {
+        out_tangent: Point::new(0.42, 0.0),
+        in_tangent: Point::new(0.58, 1.0),
+    }));
+    // Symmetric curve passes through the midpoint.
+    assert!((eased.evaluate(5.0) - 50.0).abs() < 1e-2);
+    // Slow start and slow end.
+    assert!(eased.evaluate(2.0) < 20.0);
+    assert!(eased.evaluate(8.0) > 80.0);
+    assert_eq!(eased.evaluate(0.0), 0.0);
+    assert!((eased.evaluate(10.0) - 100.0).abs() < 1e-3);
+}
+
+#[test]
+fn test_component_easing() {
+    let ease_in = Easing {
+        out_tangent: Point::new(0.9, 0.0),
+        in_tangent: Point::new(1.0, 1.0),
+    };
+    let animated = Animated {
+        times: vec![
+            Time {
+                frame: 0.0,
+                hold: false,
+                easing: Some(Easing::LINEAR),
+                component_easing: Some([Easing::LINEAR, ease_in, ease_in]),
+            },
+            Time {
+                frame: 10.0,
+                hold: false,
+                easing: None,
+                component_easing: None,
+            },
+        ],
+        values: vec![Point::new(0.0, 0.0), Point::new(100.0, 100.0)],
+    };
+    let value = animated.evaluate(5.0);
+    assert!((value.x - 50.0).abs() < 1e-3);
+    assert!(value.y < 25.0);
+}
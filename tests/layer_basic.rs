@@ -213,6 +213,7 @@ lazy_static! {
                     css_class: None,
                     xml_id: None
                 },
+                direction: None,
                 position: Position {
                     property_index: None,
                     animated: Some(BoolInt::False),
@@ -0,0 +1,110 @@
+use serde_json::json;
+use vellottie::runtime::{
+    model::fixed,
+    vello::{
+        kurbo::{Affine, Shape},
+        peniko::{BlendMode, Compose, Fill, Mix},
+    },
+    Composition, RenderSink, Renderer,
+};
+
+/// Render sink that records the blend modes of the pushed layers.
+#[derive(Default)]
+struct Layers {
+    blends: Vec<BlendMode>,
+    depth: usize,
+}
+
+impl RenderSink for Layers {
+    fn push_layer(
+        &mut self,
+        blend: impl Into<BlendMode>,
+        _alpha: f32,
+        _transform: Affine,
+        _shape: &impl Shape,
+    ) {
+        self.blends.push(blend.into());
+        self.depth += 1;
+    }
+
+    fn pop_layer(&mut self) {
+        self.depth -= 1;
+    }
+
+    fn draw(
+        &mut self,
+        _stroke: Option<&fixed::Stroke>,
+        _fill: Fill,
+        _transform: Affine,
+        _brush: &fixed::Brush,
+        _shape: &impl Shape,
+    ) {
+    }
+}
+
+/// Blend modes pushed when rendering a square layer with the blend mode
+/// `bm`.
+fn layer_blends(bm: u8) -> Vec<BlendMode> {
+    let static_value = |value| json!({ "a": 0, "k": value });
+    let lottie = json!({
+        "v": "5.5.2",
+        "fr": 10,
+        "ip": 0,
+        "op": 20,
+        "w": 100,
+        "h": 100,
+        "layers": [{
+            "ty": 4,
+            "ind": 1,
+            "st": 0,
+            "ip": 0,
+            "op": 20,
+            "bm": bm,
+            "ks": {
+                "a": static_value(json!([0, 0])),
+                "p": static_value(json!([0, 0])),
+                "s": static_value(json!([100, 100])),
+                "r": static_value(json!(0)),
+                "o": static_value(json!(100))
+            },
+            "shapes": [
+                {
+                    "ty": "rc",
+                    "p": static_value(json!([5, 5])),
+                    "s": static_value(json!([10, 10])),
+                    "r": static_value(json!(0))
+                },
+                {
+                    "ty": "fl",
+                    "c": static_value(json!([1, 0, 0])),
+                    "o": static_value(json!(100))
+                }
+            ]
+        }]
+    });
+    let composition =
+        Composition::from_bytes(serde_json::to_vec(&lottie).unwrap()).unwrap();
+    let mut sink = Layers::default();
+    Renderer::new().render_frame(
+        &composition,
+        0.0,
+        Affine::IDENTITY,
+        1.0,
+        &mut sink,
+    );
+    // Every pushed layer is popped again.
+    assert_eq!(sink.depth, 0);
+    sink.blends
+}
+
+#[test]
+fn test_layer_blend_mode() {
+    assert!(layer_blends(0).is_empty());
+    assert_eq!(layer_blends(1), [BlendMode::from(Mix::Multiply)]);
+    assert_eq!(layer_blends(2), [BlendMode::from(Mix::Screen)]);
+    assert_eq!(
+        layer_blends(16),
+        [BlendMode::new(Mix::Normal, Compose::Plus)]
+    );
+    assert_eq!(layer_blends(17).len(), 1);
+}
@@ -0,0 +1,152 @@
+use serde_json::json;
+use vellottie::runtime::{
+    model::fixed,
+    vello::{
+        kurbo::{Affine, Rect, Shape},
+        peniko::{BlendMode, Compose, Fill},
+    },
+    Composition, RenderSink, Renderer,
+};
+
+#[derive(Debug, PartialEq)]
+enum Op {
+    Push(Compose),
+    Pop,
+    Draw(Rect),
+}
+
+/// Render sink that records layer compositions and draw bounds.
+#[derive(Default)]
+struct Ops(Vec<Op>);
+
+impl RenderSink for Ops {
+    fn push_layer(
+        &mut self,
+        blend: impl Into<BlendMode>,
+        _alpha: f32,
+        _transform: Affine,
+        _shape: &impl Shape,
+    ) {
+        self.0.push(Op::Push(blend.into().compose));
+    }
+
+    fn pop_layer(&mut self) {
+        self.0.push(Op::Pop);
+    }
+
+    fn draw(
+        &mut self,
+        _stroke: Option<&fixed::Stroke>,
+        _fill: Fill,
+        transform: Affine,
+        _brush: &fixed::Brush,
+        shape: &impl Shape,
+    ) {
+        let bounds = transform.transform_rect_bbox(shape.bounding_box());
+        self.0.push(Op::Draw(bounds));
+    }
+}
+
+fn static_value(value: serde_json::Value) -> serde_json::Value {
+    json!({ "a": 0, "k": value })
+}
+
+/// Operations of a filled 10x10 square layer with an additive mask over
+/// the square from (2, 2) to (8, 8).
+fn render_masked(inverted: bool, expansion: f32) -> Vec<Op> {
+    let mask = json!({
+        "mode": "a",
+        "inv": inverted,
+        "o": static_value(json!(100)),
+        "x": static_value(json!(expansion)),
+        "pt": static_value(json!({
+            "c": true,
+            "v": [[2, 2], [8, 2], [8, 8], [2, 8]],
+            "i": [[0, 0], [0, 0], [0, 0], [0, 0]],
+            "o": [[0, 0], [0, 0], [0, 0], [0, 0]]
+        }))
+    });
+    let lottie = json!({
+        "v": "5.5.2",
+        "fr": 10,
+        "ip": 0,
+        "op": 20,
+        "w": 100,
+        "h": 100,
+        "layers": [{
+            "ty": 4,
+            "ind": 1,
+            "st": 0,
+            "ip": 0,
+            "op": 20,
+            "hasMask": true,
+            "masksProperties": [mask],
+            "ks": {
+                "a": static_value(json!([0, 0])),
+                "p": static_value(json!([0, 0])),
+                "s": static_value(json!([100, 100])),
+                "r": static_value(json!(0)),
+                "o": static_value(json!(100))
+            },
+            "shapes": [
+                {
+                    "ty": "rc",
+                    "p": static_value(json!([5, 5])),
+                    "s": static_value(json!([10, 10])),
+                    "r": static_value(json!(0))
+                },
+                {
+                    "ty": "fl",
+                    "c": static_value(json!([1, 0, 0])),
+                    "o": static_value(json!(100))
+                }
+            ]
+        }]
+    });
+    let composition =
+        Composition::from_bytes(serde_json::to_vec(&lottie).unwrap()).unwrap();
+    let mut sink = Ops::default();
+    Renderer::new().render_frame(
+        &composition,
+        0.0,
+        Affine::IDENTITY,
+        1.0,
+        &mut sink,
+    );
+    sink.0
+}
+
+#[test]
+fn test_inverted_mask() {
+    // The mask is removed from full coverage of the layer.
+    assert_eq!(
+        render_masked(true, 0.0),
+        [
+            Op::Push(Compose::SrcOver),
+            Op::Draw(Rect::new(0.0, 0.0, 10.0, 10.0)),
+            Op::Push(Compose::DestIn),
+            Op::Push(Compose::SrcOver),
+            Op::Draw(Rect::new(0.0, 0.0, 100.0, 100.0)),
+            Op::Push(Compose::DestOut),
+            Op::Draw(Rect::new(2.0, 2.0, 8.0, 8.0)),
+            Op::Pop,
+            Op::Pop,
+            Op::Pop,
+            Op::Pop,
+        ]
+    );
+}
+
+#[test]
+fn test_mask_expansion() {
+    let expanded = |expansion| {
+        // The content is drawn first, followed by the mask.
+        match render_masked(false, expansion).as_slice() {
+            [.., Op::Draw(bounds), Op::Pop, Op::Pop, Op::Pop] => *bounds,
+            ops => panic!("unexpected operations: {ops:?}"),
+        }
+    };
+    assert_eq!(expanded(0.0), Rect::new(2.0, 2.0, 8.0, 8.0));
+    assert_eq!(expanded(1.0), Rect::new(1.0, 1.0, 9.0, 9.0));
+    assert_eq!(expanded(-1.0), Rect::new(3.0, 3.0, 7.0, 7.0));
+}
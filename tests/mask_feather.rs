@@ -0,0 +1,171 @@
+use serde_json::json;
+use vellottie::parser::{breadcrumb::Breadcrumb, schema::helpers::mask::Mask};
+use vellottie::runtime::{
+    model::fixed,
+    vello::{
+        kurbo::{Affine, Rect, Shape},
+        peniko::{BlendMode, Fill},
+    },
+    Composition, RenderSink, Renderer,
+};
+
+/// Render sink that records the bounds and alpha of each solid draw.
+#[derive(Default)]
+struct Draws(Vec<(Rect, f64)>);
+
+impl RenderSink for Draws {
+    fn push_layer(
+        &mut self,
+        _blend: impl Into<BlendMode>,
+        _alpha: f32,
+        _transform: Affine,
+        _shape: &impl Shape,
+    ) {
+    }
+
+    fn pop_layer(&mut self) {}
+
+    fn draw(
+        &mut self,
+        _stroke: Option<&fixed::Stroke>,
+        _fill: Fill,
+        transform: Affine,
+        brush: &fixed::Brush,
+        shape: &impl Shape,
+    ) {
+        let fixed::Brush::Solid(color) = brush else {
+            panic!("expected a solid brush");
+        };
+        let bounds = transform.transform_rect_bbox(shape.bounding_box());
+        self.0.push((bounds, color.a as f64 / 255.0));
+    }
+}
+
+fn static_value(value: serde_json::Value) -> serde_json::Value {
+    json!({ "a": 0, "k": value })
+}
+
+/// Additive mask over the square from (2, 2) to (8, 8).
+fn feathered_mask(feather: serde_json::Value) -> serde_json::Value {
+    json!({
+        "mode": "a",
+        "o": static_value(json!(100)),
+        "f": static_value(feather),
+        "pt": static_value(json!({
+            "c": true,
+            "v": [[2, 2], [8, 2], [8, 8], [2, 8]],
+            "i": [[0, 0], [0, 0], [0, 0], [0, 0]],
+            "o": [[0, 0], [0, 0], [0, 0], [0, 0]]
+        }))
+    })
+}
+
+/// Bounds and alpha of the copies drawn for `mask` on a filled square.
+fn mask_copies(mask: serde_json::Value) -> Vec<(Rect, f64)> {
+    let lottie = json!({
+        "v": "5.5.2",
+        "fr": 10,
+        "ip": 0,
+        "op": 20,
+        "w": 100,
+        "h": 100,
+        "layers": [{
+            "ty": 4,
+            "ind": 1,
+            "st": 0,
+            "ip": 0,
+            "op": 20,
+            "hasMask": true,
+            "masksProperties": [mask],
+            "ks": {
+                "a": static_value(json!([0, 0])),
+                "p": static_value(json!([0, 0])),
+                "s": static_value(json!([100, 100])),
+                "r": static_value(json!(0)),
+                "o": static_value(json!(100))
+            },
+            "shapes": [
+                {
+                    "ty": "rc",
+                    "p": static_value(json!([5, 5])),
+                    "s": static_value(json!([10, 10])),
+                    "r": static_value(json!(0))
+                },
+                {
+                    "ty": "fl",
+                    "c": static_value(json!([1, 0, 0])),
+                    "o": static_value(json!(100))
+                }
+            ]
+        }]
+    });
+    let composition =
+        Composition::from_bytes(serde_json::to_vec(&lottie).unwrap()).unwrap();
+    let mut sink = Draws::default();
+    Renderer::new().render_frame(
+        &composition,
+        0.0,
+        Affine::IDENTITY,
+        1.0,
+        &mut sink,
+    );
+    // The content is drawn first, followed by the copies of the mask.
+    sink.0.split_off(1)
+}
+
+#[test]
+fn test_parse_mask_feather() {
+    let mask = feathered_mask(json!([4, 4]));
+    let parsed = Mask::from_json(&mut Breadcrumb::new(), &mask).unwrap();
+    assert!(parsed.feather.is_some());
+}
+
+#[test]
+fn test_mask_feather() {
+    // About one copy is drawn per pixel of the feather.
+    let draws = mask_copies(feathered_mask(json!([4, 4])));
+    assert_eq!(draws.len(), 4);
+    // The copies span the feather from the outside to the inside.
+    let (outer, _) = draws[0];
+    let (inner, _) = draws[3];
+    assert!((outer.x0 - 0.5).abs() < 0.01 && (outer.x1 - 9.5).abs() < 0.01);
+    assert!((inner.x0 - 3.5).abs() < 0.01 && (inner.x1 - 6.5).abs() < 0.01);
+    // Stacking the copies raises the coverage linearly to fully opaque.
+    let mut coverage = 0.0;
+    for (i, (_, alpha)) in draws.iter().enumerate() {
+        coverage += (1.0 - coverage) * alpha;
+        assert!((coverage - (i + 1) as f64 / 4.0).abs() < 0.02);
+    }
+    // Feathers narrower than a pixel are drawn as a single copy.
+    let draws = mask_copies(feathered_mask(json!([0.5, 0.5])));
+    assert_eq!(draws, [(Rect::new(2.0, 2.0, 8.0, 8.0), 1.0)]);
+}
+
+#[test]
+fn test_uneven_mask_feather() {
+    // Only the horizontal edges are feathered.
+    let draws = mask_copies(feathered_mask(json!([4, 0])));
+    assert_eq!(draws.len(), 4);
+    let (outer, _) = draws[0];
+    let (inner, _) = draws[3];
+    assert!((outer.x0 - 0.5).abs() < 0.01 && (outer.x1 - 9.5).abs() < 0.01);
+    assert!((inner.x0 - 3.5).abs() < 0.01 && (inner.x1 - 6.5).abs() < 0.01);
+    for (bounds, _) in draws {
+        assert!((bounds.y0 - 2.0).abs() < 0.01, "{bounds:?}");
+        assert!((bounds.y1 - 8.0).abs() < 0.01, "{bounds:?}");
+    }
+    // The vertical edges are feathered half as much.
+    let draws = mask_copies(feathered_mask(json!([4, 2])));
+    let (outer, _) = draws[0];
+    assert!((outer.x0 - 0.5).abs() < 0.01 && (outer.y0 - 1.25).abs() < 0.01);
+}
+
+#[test]
+fn test_mask_feather_opacity() {
+    // Opacity above 100 is clamped.
+    let mut mask = feathered_mask(json!([4, 4]));
+    mask["o"] = static_value(json!(150));
+    let draws = mask_copies(mask);
+    assert!(draws.iter().all(|(_, alpha)| (0.0..=1.0).contains(alpha)));
+    assert_eq!(draws.last().unwrap().1, 1.0);
+}
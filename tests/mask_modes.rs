@@ -0,0 +1,226 @@
+use serde_json::json;
+use vellottie::runtime::{
+    model::fixed,
+    vello::{
+        kurbo::{Affine, BezPath, Point, Shape},
+        peniko::{BlendMode, Compose, Fill, Mix},
+    },
+    Composition, RenderSink, Renderer,
+};
+
+enum Op {
+    Push(BlendMode, f32),
+    Pop,
+    /// Path of a draw and the alpha of its solid brush.
+    Draw(BezPath, f64),
+}
+
+/// Render sink that records layers and draws.
+#[derive(Default)]
+struct Ops(Vec<Op>);
+
+impl RenderSink for Ops {
+    fn push_layer(
+        &mut self,
+        blend: impl Into<BlendMode>,
+        alpha: f32,
+        _transform: Affine,
+        _shape: &impl Shape,
+    ) {
+        self.0.push(Op::Push(blend.into(), alpha));
+    }
+
+    fn pop_layer(&mut self) {
+        self.0.push(Op::Pop);
+    }
+
+    fn draw(
+        &mut self,
+        _stroke: Option<&fixed::Stroke>,
+        _fill: Fill,
+        transform: Affine,
+        brush: &fixed::Brush,
+        shape: &impl Shape,
+    ) {
+        let fixed::Brush::Solid(color) = brush else {
+            panic!("expected a solid brush");
+        };
+        let path = transform * shape.to_path(0.1);
+        self.0.push(Op::Draw(path, color.a as f64 / 255.0));
+    }
+}
+
+fn static_value(value: serde_json::Value) -> serde_json::Value {
+    json!({ "a": 0, "k": value })
+}
+
+/// Mask covering the square from (2, 2) to (8, 8).
+fn mask(mode: &str, opacity: f32) -> serde_json::Value {
+    json!({
+        "mode": mode,
+        "o": static_value(json!(opacity)),
+        "pt": static_value(json!({
+            "c": true,
+            "v": [[2, 2], [8, 2], [8, 8], [2, 8]],
+            "i": [[0, 0], [0, 0], [0, 0], [0, 0]],
+            "o": [[0, 0], [0, 0], [0, 0], [0, 0]]
+        }))
+    })
+}
+
+/// Operations of a filled 10x10 square layer with `masks`.
+fn render_masked(masks: serde_json::Value) -> Vec<Op> {
+    let lottie = json!({
+        "v": "5.5.2",
+        "fr": 10,
+        "ip": 0,
+        "op": 20,
+        "w": 100,
+        "h": 100,
+        "layers": [{
+            "ty": 4,
+            "ind": 1,
+            "st": 0,
+            "ip": 0,
+            "op": 20,
+            "hasMask": true,
+            "masksProperties": masks,
+            "ks": {
+                "a": static_value(json!([0, 0])),
+                "p": static_value(json!([0, 0])),
+                "s": static_value(json!([100, 100])),
+                "r": static_value(json!(0)),
+                "o": static_value(json!(100))
+            },
+            "shapes": [
+                {
+                    "ty": "rc",
+                    "p": static_value(json!([5, 5])),
+                    "s": static_value(json!([10, 10])),
+                    "r": static_value(json!(0))
+                },
+                {
+                    "ty": "fl",
+                    "c": static_value(json!([1, 0, 0])),
+                    "o": static_value(json!(100))
+                }
+            ]
+        }]
+    });
+    let composition =
+        Composition::from_bytes(serde_json::to_vec(&lottie).unwrap()).unwrap();
+    let mut sink = Ops::default();
+    Renderer::new().render_frame(
+        &composition,
+        0.0,
+        Affine::IDENTITY,
+        1.0,
+        &mut sink,
+    );
+    sink.0
+}
+
+#[test]
+fn test_mask_modes() {
+    // Layers pushed and whether each draw covers the full composition.
+    let ops = |masks| {
+        render_masked(masks)
+            .iter()
+            .map(|op| match op {
+                Op::Push(blend, _) => format!("{:?}", blend.compose),
+                Op::Pop => "Pop".to_string(),
+                Op::Draw(path, _) if path.bounding_box().width() == 100.0 => {
+                    "Full".to_string()
+                }
+                Op::Draw(..) => "Draw".to_string(),
+            })
+            .collect::<Vec<_>>()
+    };
+    // The content is isolated and then multiplied by the mask coverage.
+    assert_eq!(
+        ops(json!([mask("a", 100.0), mask("s", 100.0)])),
+        [
+            "SrcOver", "Draw", "DestIn", "SrcOver", "Draw", "Pop", "DestOut",
+            "Draw", "Pop", "Pop", "Pop"
+        ]
+    );
+    // Subtracting first removes the mask from full coverage.
+    assert_eq!(
+        ops(json!([mask("s", 100.0)])),
+        [
+            "SrcOver", "Draw", "DestIn", "Full", "DestOut", "Draw", "Pop",
+            "Pop", "Pop"
+        ]
+    );
+    let masked = render_masked(json!([mask("i", 100.0), mask("f", 100.0)]));
+    let blends = masked.iter().filter_map(|op| match op {
+        Op::Push(blend, _) => Some(*blend),
+        _ => None,
+    });
+    assert_eq!(
+        blends.collect::<Vec<_>>(),
+        [
+            BlendMode::from(Mix::Normal),
+            BlendMode::new(Mix::Normal, Compose::DestIn),
+            BlendMode::new(Mix::Normal, Compose::DestIn),
+            BlendMode::new(Mix::Normal, Compose::Xor),
+        ]
+    );
+    // Masks without a mode are ignored.
+    assert_eq!(ops(json!([mask("n", 100.0)])), ["Draw"]);
+}
+
+/// Computes the alpha at a point of the composited operations.
+fn coverage(ops: &[Op], point: Point) -> f64 {
+    let composite = |compose, s: f64, d: f64| match compose {
+        Compose::SrcOver => s + d * (1.0 - s),
+        Compose::DestIn => d * s,
+        Compose::DestOut => d * (1.0 - s),
+        Compose::Xor => s * (1.0 - d) + d * (1.0 - s),
+        compose => panic!("unsupported composition: {compose:?}"),
+    };
+    // Alpha of each layer with its composition and opacity.
+    let mut layers = vec![(0.0, Compose::SrcOver, 1.0)];
+    for op in ops {
+        match op {
+            Op::Push(blend, alpha) => {
+                layers.push((0.0, blend.compose, *alpha as f64));
+            }
+            Op::Pop => {
+                let (s, compose, alpha) = layers.pop().unwrap();
+                let d = &mut layers.last_mut().unwrap().0;
+                *d = composite(compose, s * alpha, *d);
+            }
+            Op::Draw(path, alpha) => {
+                if path.winding(point) != 0 {
+                    let d = &mut layers.last_mut().unwrap().0;
+                    *d = composite(Compose::SrcOver, *alpha, *d);
+                }
+            }
+        }
+    }
+    layers[0].0
+}
+
+#[test]
+fn test_mask_modes_partial_opacity() {
+    // Coverage of a mask at 60% combined with one at 50%.
+    let combined = |mode| {
+        let ops = render_masked(json!([mask("a", 60.0), mask(mode, 50.0)]));
+        coverage(&ops, Point::new(5.0, 5.0))
+    };
+    let expected = [
+        ("a", 0.8),
+        ("s", 0.3),
+        ("i", 0.3),
+        // Lighten, darken and difference are approximated by add, intersect
+        // and exclusive or, see MaskMode.
+        ("l", 0.8),
+        ("d", 0.3),
+        ("f", 0.5),
+    ];
+    for (mode, expected) in expected {
+        let coverage = combined(mode);
+        assert!((coverage - expected).abs() < 0.01, "{mode}: {coverage}");
+    }
+}
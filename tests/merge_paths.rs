@@ -0,0 +1,191 @@
+use serde_json::json;
+use vellottie::runtime::{
+    model::fixed,
+    vello::{
+        kurbo::{Affine, BezPath, Rect, Shape},
+        peniko::{BlendMode, Fill},
+    },
+    Composition, RenderSink, Renderer,
+};
+
+/// Render sink that records the path of each fill.
+#[derive(Default)]
+struct Paths(Vec<BezPath>);
+
+impl RenderSink for Paths {
+    fn push_layer(
+        &mut self,
+        _blend: impl Into<BlendMode>,
+        _alpha: f32,
+        _transform: Affine,
+        _shape: &impl Shape,
+    ) {
+    }
+
+    fn pop_layer(&mut self) {}
+
+    fn draw(
+        &mut self,
+        _stroke: Option<&fixed::Stroke>,
+        _fill: Fill,
+        transform: Affine,
+        _brush: &fixed::Brush,
+        shape: &impl Shape,
+    ) {
+        self.0.push(transform * shape.to_path(0.1));
+    }
+}
+
+fn static_value(value: serde_json::Value) -> serde_json::Value {
+    json!({ "a": 0, "k": value })
+}
+
+/// Composition merging a 10x10 square of size `size` with a 10x10 square
+/// moved by `position`, and filling the result.
+fn import_merge(
+    size: f64,
+    position: serde_json::Value,
+    mode: u8,
+) -> Composition {
+    let square = |size: f64| {
+        json!({
+            "ty": "rc",
+            "p": static_value(json!([size / 2.0, size / 2.0])),
+            "s": static_value(json!([size, size])),
+            "r": static_value(json!(0))
+        })
+    };
+    let lottie = json!({
+        "v": "5.5.2",
+        "fr": 10,
+        "ip": 0,
+        "op": 20,
+        "w": 100,
+        "h": 100,
+        "layers": [{
+            "ty": 4,
+            "ind": 1,
+            "st": 0,
+            "ip": 0,
+            "op": 20,
+            "ks": {
+                "a": static_value(json!([0, 0])),
+                "p": static_value(json!([0, 0])),
+                "s": static_value(json!([100, 100])),
+                "r": static_value(json!(0)),
+                "o": static_value(json!(100))
+            },
+            "shapes": [
+                square(size),
+                {
+                    "ty": "gr",
+                    "it": [
+                        square(10.0),
+                        {
+                            "ty": "tr",
+                            "a": static_value(json!([0, 0])),
+                            "p": position,
+                            "s": static_value(json!([100, 100])),
+                            "r": static_value(json!(0)),
+                            "o": static_value(json!(100))
+                        }
+                    ]
+                },
+                { "ty": "mm", "mm": mode },
+                {
+                    "ty": "fl",
+                    "c": static_value(json!([1, 0, 0])),
+                    "o": static_value(json!(100))
+                }
+            ]
+        }]
+    });
+    Composition::from_bytes(serde_json::to_vec(&lottie).unwrap()).unwrap()
+}
+
+fn render_paths(
+    renderer: &mut Renderer,
+    composition: &Composition,
+    frame: f32,
+) -> Vec<BezPath> {
+    let mut sink = Paths::default();
+    renderer.render_frame(composition, frame, Affine::IDENTITY, 1.0, &mut sink);
+    sink.0
+}
+
+#[test]
+fn test_merge_paths() {
+    // Number of paths, area and bounds of two merged squares.
+    let merged = |offset: (f64, f64), mode| {
+        let position = static_value(json!([offset.0, offset.1]));
+        let composition = import_merge(10.0, position, mode);
+        let paths = render_paths(&mut Renderer::new(), &composition, 0.0);
+        let area = paths.iter().map(|path| path.area()).sum::<f64>().abs();
+        let bounds = paths
+            .iter()
+            .map(|path| path.bounding_box())
+            .reduce(|a, b| a.union(b))
+            .unwrap_or_default();
+        (paths.len(), area, bounds)
+    };
+    // Merge keeps both paths.
+    assert_eq!(merged((5.0, 0.0), 1).0, 2);
+    // Add, subtract, intersect and exclude of overlapping squares.
+    let (count, area, bounds) = merged((5.0, 0.0), 2);
+    assert_eq!(count, 1);
+    assert!((area - 150.0).abs() < 1e-9);
+    assert_eq!(bounds, Rect::new(0.0, 0.0, 15.0, 10.0));
+    let (_, area, bounds) = merged((5.0, 0.0), 3);
+    assert!((area - 50.0).abs() < 1e-9);
+    assert_eq!(bounds, Rect::new(0.0, 0.0, 5.0, 10.0));
+    let (_, area, bounds) = merged((5.0, 5.0), 4);
+    assert!((area - 25.0).abs() < 1e-9);
+    assert_eq!(bounds, Rect::new(5.0, 5.0, 10.0, 10.0));
+    let (_, area, bounds) = merged((5.0, 0.0), 5);
+    assert!((area - 100.0).abs() < 1e-9);
+    assert_eq!(bounds, Rect::new(0.0, 0.0, 15.0, 10.0));
+    // Disjoint squares do not intersect.
+    let (_, area, _) = merged((20.0, 0.0), 4);
+    assert_eq!(area, 0.0);
+}
+
+#[test]
+fn test_merge_paths_animated() {
+    // The second square moves from 0 to 10 over the first 10 frames.
+    let position = json!({
+        "a": 1,
+        "k": [
+            {
+                "t": 0,
+                "s": [0, 0],
+                "i": { "x": [1], "y": [1] },
+                "o": { "x": [0], "y": [0] }
+            },
+            { "t": 10, "s": [10, 0] }
+        ]
+    });
+    let composition = import_merge(10.0, position, 2);
+    // Results of previous frames are not reused for different operands.
+    let mut renderer = Renderer::new();
+    for (frame, expected) in [(0.0, 100.0), (5.0, 150.0), (0.0, 100.0)] {
+        let [path] = &render_paths(&mut renderer, &composition, frame)[..]
+        else {
+            panic!("expected a single draw");
+        };
+        assert!((path.area().abs() - expected).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn test_merge_paths_hole() {
+    // Subtracting a square inside a larger one leaves a hole.
+    let composition = import_merge(20.0, static_value(json!([5, 5])), 3);
+    let [path] = &render_paths(&mut Renderer::new(), &composition, 0.0)[..]
+    else {
+        panic!("expected a single draw");
+    };
+    assert_eq!(path.segments().count(), 8);
+    assert!((path.area().abs() - 300.0).abs() < 1e-9);
+    assert_eq!(path.winding((10.0, 10.0).into()), 0);
+    assert_ne!(path.winding((2.0, 10.0).into()), 0);
+}
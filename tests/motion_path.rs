@@ -0,0 +1,109 @@
+use vellottie::runtime::model::{animated::MotionPath, Time};
+use vellottie::runtime::vello::kurbo::{Point, Vec2};
+
+fn time(frame: f32) -> Time {
+    Time {
+        frame,
+        hold: false,
+        easing: None,
+        component_easing: None,
+    }
+}
+
+#[test]
+fn test_curved_motion_path() {
+    // A symmetric arc bulging upwards between two keyframes.
+    let path = MotionPath {
+        times: vec![time(0.0), time(10.0)],
+        values: vec![Point::new(0.0, 0.0), Point::new(100.0, 0.0)],
+        out_tangents: vec![Vec2::new(0.0, -50.0), Vec2::ZERO],
+        in_tangents: vec![Vec2::new(0.0, -50.0), Vec2::ZERO],
+    };
+    assert!(path.evaluate(0.0).distance(Point::new(0.0, 0.0)) < 1e-6);
+    assert!(path.evaluate(10.0).distance(Point::new(100.0, 0.0)) < 1e-6);
+    // Halfway along the arc is its apex rather than the straight line
+    // midpoint.
+    let middle = path.evaluate(5.0);
+    assert!((middle.x - 50.0).abs() < 1e-2);
+    assert!((middle.y + 37.5).abs() < 1e-2);
+}
+
+#[test]
+fn test_straight_motion_path() {
+    let path = MotionPath {
+        times: vec![time(0.0), time(10.0)],
+        values: vec![Point::new(0.0, 0.0), Point::new(100.0, 0.0)],
+        out_tangents: vec![Vec2::ZERO, Vec2::ZERO],
+        in_tangents: vec![Vec2::ZERO, Vec2::ZERO],
+    };
+    assert!(path.evaluate(2.5).distance(Point::new(25.0, 0.0)) < 1e-6);
+}
+
+#[test]
+fn test_auto_orient() {
+    use vellottie::runtime::model::{
+        animated::{Position, Rotation, Transform},
+        Animated, Value,
+    };
+    let transform = Transform {
+        anchor: Value::Fixed(Point::ZERO),
+        position: Position::Value(Value::Animated(Animated {
+            times: vec![time(0.0), time(10.0)],
+            values: vec![Point::new(0.0, 0.0), Point::new(0.0, 100.0)],
+        })),
+        rotation: Rotation::Value(Value::Fixed(0.0)),
+        scale: Value::Fixed(Vec2::new(100.0, 100.0)),
+        skew: Value::Fixed(0.0),
+        skew_angle: Value::Fixed(0.0),
+    };
+    // Moving straight down rotates the x axis to point down.
+    let affine = transform.evaluate_auto_oriented(5.0);
+    let x_axis = affine * Point::new(1.0, 0.0) - affine * Point::ZERO;
+    assert!(x_axis.x.abs() < 1e-6);
+    assert!((x_axis.y - 1.0).abs() < 1e-6);
+    // Without auto-orientation the content is not rotated.
+    let affine = transform.evaluate(5.0);
+    let x_axis = affine * Point::new(1.0, 0.0) - affine * Point::ZERO;
+    assert!((x_axis.x - 1.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_auto_orient_hold() {
+    use vellottie::runtime::model::{
+        animated::{Position, Rotation, Transform},
+        Animated, Value,
+    };
+    // Moves down, stands still, holds and then moves right.
+    let mut hold = time(20.0);
+    hold.hold = true;
+    let transform = Transform {
+        anchor: Value::Fixed(Point::ZERO),
+        position: Position::Value(Value::Animated(Animated {
+            times: vec![time(0.0), time(10.0), hold, time(30.0), time(40.0)],
+            values: vec![
+                Point::new(0.0, 0.0),
+                Point::new(0.0, 100.0),
+                Point::new(0.0, 100.0),
+                Point::new(0.0, 100.0),
+                Point::new(100.0, 100.0),
+            ],
+        })),
+        rotation: Rotation::Value(Value::Fixed(0.0)),
+        scale: Value::Fixed(Vec2::new(100.0, 100.0)),
+        skew: Value::Fixed(0.0),
+        skew_angle: Value::Fixed(0.0),
+    };
+    let x_axis = |frame| {
+        let affine = transform.evaluate_auto_oriented(frame);
+        affine * Point::new(1.0, 0.0) - affine * Point::ZERO
+    };
+    // The content keeps pointing down until it moves right.
+    for frame in [5.0, 15.0, 25.0] {
+        let x_axis = x_axis(frame);
+        assert!(x_axis.x.abs() < 1e-6, "{frame}: {x_axis:?}");
+        assert!((x_axis.y - 1.0).abs() < 1e-6, "{frame}: {x_axis:?}");
+    }
+    let x_axis = x_axis(35.0);
+    assert!((x_axis.x - 1.0).abs() < 1e-6);
+    assert!(x_axis.y.abs() < 1e-6);
+}
@@ -0,0 +1,145 @@
+use serde_json::json;
+use vellottie::parser::{breadcrumb::Breadcrumb, schema::shapes::AnyShape};
+use vellottie::runtime::{
+    model::fixed,
+    vello::{
+        kurbo::{Affine, BezPath, Rect, Shape},
+        peniko::{BlendMode, Fill},
+    },
+    Composition, RenderSink, Renderer,
+};
+
+/// Render sink that records the path of each draw.
+#[derive(Default)]
+struct Paths(Vec<BezPath>);
+
+impl RenderSink for Paths {
+    fn push_layer(
+        &mut self,
+        _blend: impl Into<BlendMode>,
+        _alpha: f32,
+        _transform: Affine,
+        _shape: &impl Shape,
+    ) {
+    }
+
+    fn pop_layer(&mut self) {}
+
+    fn draw(
+        &mut self,
+        _stroke: Option<&fixed::Stroke>,
+        _fill: Fill,
+        transform: Affine,
+        _brush: &fixed::Brush,
+        shape: &impl Shape,
+    ) {
+        self.0.push(transform * shape.to_path(0.1));
+    }
+}
+
+fn static_value(value: serde_json::Value) -> serde_json::Value {
+    json!({ "a": 0, "k": value })
+}
+
+fn offset(amount: f32, join: u8) -> serde_json::Value {
+    json!({
+        "ty": "op",
+        "a": static_value(json!(amount)),
+        "lj": join,
+        "ml": static_value(json!(4))
+    })
+}
+
+/// Paths drawn for a filled 10x10 square with `modifiers` applied.
+fn modified_square(modifiers: &[serde_json::Value]) -> Vec<BezPath> {
+    let mut shapes = vec![json!({
+        "ty": "rc",
+        "p": static_value(json!([5, 5])),
+        "s": static_value(json!([10, 10])),
+        "r": static_value(json!(0))
+    })];
+    shapes.extend_from_slice(modifiers);
+    shapes.push(json!({
+        "ty": "fl",
+        "c": static_value(json!([1, 0, 0])),
+        "o": static_value(json!(100))
+    }));
+    let lottie = json!({
+        "v": "5.5.2",
+        "fr": 10,
+        "ip": 0,
+        "op": 20,
+        "w": 100,
+        "h": 100,
+        "layers": [{
+            "ty": 4,
+            "ind": 1,
+            "st": 0,
+            "ip": 0,
+            "op": 20,
+            "ks": {
+                "a": static_value(json!([0, 0])),
+                "p": static_value(json!([0, 0])),
+                "s": static_value(json!([100, 100])),
+                "r": static_value(json!(0)),
+                "o": static_value(json!(100))
+            },
+            "shapes": shapes
+        }]
+    });
+    let composition =
+        Composition::from_bytes(serde_json::to_vec(&lottie).unwrap()).unwrap();
+    let mut sink = Paths::default();
+    Renderer::new().render_frame(
+        &composition,
+        0.0,
+        Affine::IDENTITY,
+        1.0,
+        &mut sink,
+    );
+    sink.0
+}
+
+fn offset_square(amount: f32, join: u8) -> BezPath {
+    match &modified_square(&[offset(amount, join)])[..] {
+        [path] => path.clone(),
+        _ => panic!("expected a single draw"),
+    }
+}
+
+#[test]
+fn test_parse_offset_path() {
+    let json = offset(2.0, 1);
+    let shape = AnyShape::from_json(&mut Breadcrumb::new(), &json).unwrap();
+    assert_eq!(serde_json::from_value::<AnyShape>(json).unwrap(), shape);
+}
+
+#[test]
+fn test_offset_path() {
+    // Miter joins keep the corners square.
+    let path = offset_square(2.0, 1);
+    assert_eq!(path.bounding_box(), Rect::new(-2.0, -2.0, 12.0, 12.0));
+    assert!((path.area().abs() - 196.0).abs() < 1e-6);
+    // Round joins round them off, up to the flattening tolerance.
+    let path = offset_square(2.0, 2);
+    let area = 100.0 + 80.0 + std::f64::consts::PI * 4.0;
+    assert_eq!(path.bounding_box(), Rect::new(-2.0, -2.0, 12.0, 12.0));
+    assert!((path.area().abs() - area).abs() < 1.0);
+    // Negative amounts shrink the square.
+    let path = offset_square(-2.0, 1);
+    assert_eq!(path.bounding_box(), Rect::new(2.0, 2.0, 8.0, 8.0));
+    assert!((path.area().abs() - 36.0).abs() < 1e-6);
+}
+
+#[test]
+fn test_offset_trimmed_path() {
+    // Geometry emptied by a trim stays empty.
+    let trim = json!({
+        "ty": "tm",
+        "s": static_value(json!(50)),
+        "e": static_value(json!(50)),
+        "o": static_value(json!(0)),
+        "m": 1
+    });
+    assert!(modified_square(&[trim, offset(2.0, 1)]).is_empty());
+}
@@ -0,0 +1,147 @@
+use serde_json::json;
+use vellottie::parser::{breadcrumb::Breadcrumb, schema::shapes::AnyShape};
+use vellottie::runtime::{
+    model::{animated::Star, Content, Geometry, Shape, Value},
+    vello::kurbo::{PathEl, Point, Shape as _},
+    Composition,
+};
+
+fn star_json() -> serde_json::Value {
+    json!({
+        "ty": "sr",
+        "p": { "a": 0, "k": [50, 50] },
+        "or": { "a": 0, "k": 20 },
+        "os": { "a": 0, "k": 0 },
+        "ir": { "a": 0, "k": 10 },
+        "is": { "a": 0, "k": 0 },
+        "r": { "a": 0, "k": 0 },
+        "pt": { "a": 0, "k": 5 },
+        "sy": 1
+    })
+}
+
+fn star(is_polygon: bool, points: f32, roundness: f32) -> Star {
+    Star {
+        is_polygon,
+        is_ccw: false,
+        position: Value::Fixed(Point::new(0.0, 0.0)),
+        inner_radius: Value::Fixed(5.0),
+        inner_roundness: Value::Fixed(roundness),
+        outer_radius: Value::Fixed(10.0),
+        outer_roundness: Value::Fixed(roundness),
+        rotation: Value::Fixed(0.0),
+        points: Value::Fixed(points),
+    }
+}
+
+fn path_vertices(path: &[PathEl]) -> Vec<Point> {
+    path.iter()
+        .filter_map(|el| match el {
+            PathEl::MoveTo(p) | PathEl::LineTo(p) => Some(*p),
+            PathEl::CurveTo(_, _, p) => Some(*p),
+            _ => None,
+        })
+        .collect()
+}
+
+fn assert_near(a: Point, b: Point) {
+    assert!((a - b).hypot() < 1e-9, "{a:?} != {b:?}");
+}
+
+#[test]
+fn test_parse_polystar() {
+    let json = star_json();
+    let serde_shape: AnyShape = serde_json::from_value(json.clone()).unwrap();
+    assert!(matches!(serde_shape, AnyShape::PolyStar(_)));
+    let shape = AnyShape::from_json(&mut Breadcrumb::new(), &json).unwrap();
+    assert_eq!(serde_shape, shape);
+    assert_eq!(serde_json::to_value(&shape).unwrap(), json);
+}
+
+#[test]
+fn test_import_polystar() {
+    let lottie = json!({
+        "v": "5.5.2",
+        "fr": 10,
+        "ip": 0,
+        "op": 20,
+        "w": 100,
+        "h": 100,
+        "layers": [{
+            "ty": 4,
+            "ind": 1,
+            "st": 0,
+            "ip": 0,
+            "op": 20,
+            "ks": {
+                "a": { "a": 0, "k": [0, 0] },
+                "p": { "a": 0, "k": [0, 0] },
+                "s": { "a": 0, "k": [100, 100] },
+                "r": { "a": 0, "k": 0 },
+                "o": { "a": 0, "k": 100 }
+            },
+            "shapes": [star_json()]
+        }]
+    });
+    let composition =
+        Composition::from_bytes(serde_json::to_vec(&lottie).unwrap()).unwrap();
+    let Content::Shape(shapes) = &composition.layers[0].content else {
+        panic!("expected shape layer");
+    };
+    let Some(Shape::Geometry(Geometry::Star(star))) = shapes.first() else {
+        panic!("expected star geometry");
+    };
+    assert!(!star.is_polygon);
+    let mut path = vec![];
+    star.evaluate(0.0, &mut path);
+    let bounds = path.as_slice().bounding_box();
+    assert!((bounds.min_y() - 30.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_star_vertices() {
+    let mut path = vec![];
+    star(false, 4.0, 0.0).evaluate(0.0, &mut path);
+    // Straight edges between alternating outer and inner vertices.
+    assert!(path[1..9].iter().all(|el| matches!(el, PathEl::LineTo(_))));
+    assert_eq!(path.last(), Some(&PathEl::ClosePath));
+    let vertices = path_vertices(&path);
+    assert_eq!(vertices.len(), 9);
+    assert_near(vertices[0], Point::new(0.0, -10.0));
+    let diagonal = 5.0 * std::f64::consts::FRAC_1_SQRT_2;
+    assert_near(vertices[1], Point::new(diagonal, -diagonal));
+    assert_near(vertices[2], Point::new(10.0, 0.0));
+    assert_near(vertices[8], vertices[0]);
+}
+
+#[test]
+fn test_polygon_vertices() {
+    let mut path = vec![];
+    let mut polygon = star(true, 4.0, 0.0);
+    polygon.rotation = Value::Fixed(45.0);
+    polygon.evaluate(0.0, &mut path);
+    let vertices = path_vertices(&path);
+    assert_eq!(vertices.len(), 5);
+    let diagonal = 10.0 * std::f64::consts::FRAC_1_SQRT_2;
+    assert_near(vertices[0], Point::new(diagonal, -diagonal));
+    assert_near(vertices[1], Point::new(diagonal, diagonal));
+
+    // Fractional points are truncated.
+    path.clear();
+    star(true, 3.7, 0.0).evaluate(0.0, &mut path);
+    assert_eq!(path_vertices(&path).len(), 4);
+}
+
+#[test]
+fn test_polygon_roundness() {
+    let mut path = vec![];
+    star(true, 4.0, 100.0).evaluate(0.0, &mut path);
+    let PathEl::CurveTo(out_tangent, in_tangent, _) = path[1] else {
+        panic!("expected rounded edge");
+    };
+    // Handles are a quarter of the perimeter segment, perpendicular to the
+    // radius and pointing along the clockwise direction of travel.
+    let handle = std::f64::consts::TAU * 10.0 / 16.0;
+    assert_near(out_tangent, Point::new(handle, -10.0));
+    assert_near(in_tangent, Point::new(10.0, -handle));
+}
@@ -0,0 +1,137 @@
+use serde_json::json;
+use vellottie::parser::{breadcrumb::Breadcrumb, schema::shapes::AnyShape};
+use vellottie::runtime::{
+    model::fixed,
+    vello::{
+        kurbo::{Affine, BezPath, PathEl, Rect, Shape},
+        peniko::{BlendMode, Fill},
+    },
+    Composition, RenderSink, Renderer,
+};
+
+/// Render sink that keeps the path of the last draw.
+#[derive(Default)]
+struct LastPath(Option<BezPath>);
+
+impl RenderSink for LastPath {
+    fn push_layer(
+        &mut self,
+        _blend: impl Into<BlendMode>,
+        _alpha: f32,
+        _transform: Affine,
+        _shape: &impl Shape,
+    ) {
+    }
+
+    fn pop_layer(&mut self) {}
+
+    fn draw(
+        &mut self,
+        _stroke: Option<&fixed::Stroke>,
+        _fill: Fill,
+        transform: Affine,
+        _brush: &fixed::Brush,
+        shape: &impl Shape,
+    ) {
+        self.0 = Some(transform * shape.to_path(0.1));
+    }
+}
+
+fn pucker_bloat_json(amount: f32) -> serde_json::Value {
+    json!({
+        "ty": "pb",
+        "a": { "a": 0, "k": amount }
+    })
+}
+
+/// Path of a filled 10x10 square with pucker and bloat applied.
+fn pucker_bloat(amount: f32) -> BezPath {
+    let static_value = |value| json!({ "a": 0, "k": value });
+    let lottie = json!({
+        "v": "5.5.2",
+        "fr": 10,
+        "ip": 0,
+        "op": 20,
+        "w": 100,
+        "h": 100,
+        "layers": [{
+            "ty": 4,
+            "ind": 1,
+            "st": 0,
+            "ip": 0,
+            "op": 20,
+            "ks": {
+                "a": static_value(json!([0, 0])),
+                "p": static_value(json!([0, 0])),
+                "s": static_value(json!([100, 100])),
+                "r": static_value(json!(0)),
+                "o": static_value(json!(100))
+            },
+            "shapes": [
+                {
+                    "ty": "rc",
+                    "p": static_value(json!([5, 5])),
+                    "s": static_value(json!([10, 10])),
+                    "r": static_value(json!(0))
+                },
+                pucker_bloat_json(amount),
+                {
+                    "ty": "fl",
+                    "c": static_value(json!([1, 0, 0])),
+                    "o": static_value(json!(100))
+                }
+            ]
+        }]
+    });
+    let composition =
+        Composition::from_bytes(serde_json::to_vec(&lottie).unwrap()).unwrap();
+    let mut sink = LastPath::default();
+    Renderer::new().render_frame(
+        &composition,
+        0.0,
+        Affine::IDENTITY,
+        1.0,
+        &mut sink,
+    );
+    sink.0.expect("expected a draw")
+}
+
+#[test]
+fn test_parse_pucker_bloat() {
+    let json = pucker_bloat_json(50.0);
+    let shape = AnyShape::from_json(&mut Breadcrumb::new(), &json).unwrap();
+    assert_eq!(serde_json::from_value::<AnyShape>(json).unwrap(), shape);
+}
+
+#[test]
+fn test_pucker_bloat() {
+    // Vertices move toward the center and control points away from it, so
+    // straight edges curve outward.
+    let path = pucker_bloat(50.0);
+    assert_eq!(path.elements()[0], PathEl::MoveTo((2.5, 2.5).into()));
+    assert_eq!(
+        path.elements()[1],
+        PathEl::CurveTo(
+            (-2.5, -2.5).into(),
+            (12.5, -2.5).into(),
+            (7.5, 2.5).into()
+        )
+    );
+    assert_eq!(path.elements().len(), 6);
+    // Negative amounts move in the opposite directions.
+    let path = pucker_bloat(-100.0);
+    assert_eq!(path.elements()[0], PathEl::MoveTo((-5.0, -5.0).into()));
+    assert_eq!(
+        path.elements()[1],
+        PathEl::CurveTo(
+            (5.0, 5.0).into(),
+            (5.0, 5.0).into(),
+            (15.0, -5.0).into()
+        )
+    );
+    // Zero leaves the square as it is.
+    assert_eq!(
+        pucker_bloat(0.0).bounding_box(),
+        Rect::new(0.0, 0.0, 10.0, 10.0)
+    );
+}
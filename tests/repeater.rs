@@ -0,0 +1,130 @@
+use serde_json::json;
+use vellottie::parser::{breadcrumb::Breadcrumb, schema::shapes::AnyShape};
+use vellottie::runtime::{
+    model::fixed,
+    vello::{
+        kurbo::{Affine, Shape},
+        peniko::{BlendMode, Fill},
+    },
+    Composition, RenderSink, Renderer,
+};
+
+/// Render sink that records the left edge and alpha of each solid fill.
+#[derive(Default)]
+struct Copies(Vec<(f64, u8)>);
+
+impl RenderSink for Copies {
+    fn push_layer(
+        &mut self,
+        _blend: impl Into<BlendMode>,
+        _alpha: f32,
+        _transform: Affine,
+        _shape: &impl Shape,
+    ) {
+    }
+
+    fn pop_layer(&mut self) {}
+
+    fn draw(
+        &mut self,
+        _stroke: Option<&fixed::Stroke>,
+        _fill: Fill,
+        transform: Affine,
+        brush: &fixed::Brush,
+        shape: &impl Shape,
+    ) {
+        let fixed::Brush::Solid(color) = brush else {
+            panic!("expected a solid fill");
+        };
+        let bounds = transform.transform_rect_bbox(shape.bounding_box());
+        self.0.push((bounds.x0, color.a));
+    }
+}
+
+fn static_value(value: serde_json::Value) -> serde_json::Value {
+    json!({ "a": 0, "k": value })
+}
+
+/// Repeater of three copies, each moved 20 to the right and fading to 40%.
+fn repeater(composite: u8) -> serde_json::Value {
+    json!({
+        "ty": "rp",
+        "c": static_value(json!(3)),
+        "o": static_value(json!(0)),
+        "m": composite,
+        "tr": {
+            "a": static_value(json!([0, 0])),
+            "p": static_value(json!([20, 0])),
+            "s": static_value(json!([100, 100])),
+            "r": static_value(json!(0)),
+            "so": static_value(json!(100)),
+            "eo": static_value(json!(40))
+        }
+    })
+}
+
+/// Copies drawn for a filled 10x10 square followed by a repeater.
+fn repeated_square(composite: u8) -> Vec<(f64, u8)> {
+    let lottie = json!({
+        "v": "5.5.2",
+        "fr": 10,
+        "ip": 0,
+        "op": 20,
+        "w": 100,
+        "h": 100,
+        "layers": [{
+            "ty": 4,
+            "ind": 1,
+            "st": 0,
+            "ip": 0,
+            "op": 20,
+            "ks": {
+                "a": static_value(json!([0, 0])),
+                "p": static_value(json!([0, 0])),
+                "s": static_value(json!([100, 100])),
+                "r": static_value(json!(0)),
+                "o": static_value(json!(100))
+            },
+            "shapes": [
+                {
+                    "ty": "rc",
+                    "p": static_value(json!([5, 5])),
+                    "s": static_value(json!([10, 10])),
+                    "r": static_value(json!(0))
+                },
+                {
+                    "ty": "fl",
+                    "c": static_value(json!([1, 0, 0])),
+                    "o": static_value(json!(100))
+                },
+                repeater(composite)
+            ]
+        }]
+    });
+    let composition =
+        Composition::from_bytes(serde_json::to_vec(&lottie).unwrap()).unwrap();
+    let mut sink = Copies::default();
+    Renderer::new().render_frame(
+        &composition,
+        0.0,
+        Affine::IDENTITY,
+        1.0,
+        &mut sink,
+    );
+    sink.0
+}
+
+#[test]
+fn test_parse_repeater() {
+    let json = repeater(1);
+    let shape = AnyShape::from_json(&mut Breadcrumb::new(), &json).unwrap();
+    assert_eq!(serde_json::from_value::<AnyShape>(json).unwrap(), shape);
+}
+
+#[test]
+fn test_repeater() {
+    // Each copy is moved further and fades toward the end opacity. Copies
+    // stacked above are drawn after the previous ones.
+    assert_eq!(repeated_square(1), [(0.0, 255), (20.0, 204), (40.0, 153)]);
+    assert_eq!(repeated_square(2), [(40.0, 153), (20.0, 204), (0.0, 255)]);
+}
@@ -0,0 +1,191 @@
+use serde_json::json;
+use vellottie::runtime::{
+    model::fixed,
+    vello::{
+        kurbo::{Affine, Rect, Shape},
+        peniko::{BlendMode, Fill, Mix},
+    },
+    Composition, RenderSink, Renderer,
+};
+
+#[derive(Debug, PartialEq)]
+enum Op {
+    Push(BlendMode, Rect),
+    Pop,
+    Fill,
+    Stroke,
+}
+
+/// Render sink that records layers and the kind of each draw.
+#[derive(Default)]
+struct Ops(Vec<Op>);
+
+impl RenderSink for Ops {
+    fn push_layer(
+        &mut self,
+        blend: impl Into<BlendMode>,
+        _alpha: f32,
+        transform: Affine,
+        shape: &impl Shape,
+    ) {
+        let bounds = transform.transform_rect_bbox(shape.bounding_box());
+        self.0.push(Op::Push(blend.into(), bounds));
+    }
+
+    fn pop_layer(&mut self) {
+        self.0.push(Op::Pop);
+    }
+
+    fn draw(
+        &mut self,
+        stroke: Option<&fixed::Stroke>,
+        _fill: Fill,
+        _transform: Affine,
+        _brush: &fixed::Brush,
+        _shape: &impl Shape,
+    ) {
+        self.0.push(match stroke {
+            Some(_) => Op::Stroke,
+            None => Op::Fill,
+        });
+    }
+}
+
+fn static_value(value: serde_json::Value) -> serde_json::Value {
+    json!({ "a": 0, "k": value })
+}
+
+fn render_shapes(shapes: serde_json::Value) -> Vec<Op> {
+    let lottie = json!({
+        "v": "5.5.2",
+        "fr": 10,
+        "ip": 0,
+        "op": 20,
+        "w": 100,
+        "h": 100,
+        "layers": [{
+            "ty": 4,
+            "ind": 1,
+            "st": 0,
+            "ip": 0,
+            "op": 20,
+            "ks": {
+                "a": static_value(json!([0, 0])),
+                "p": static_value(json!([0, 0])),
+                "s": static_value(json!([100, 100])),
+                "r": static_value(json!(0)),
+                "o": static_value(json!(100))
+            },
+            "shapes": shapes
+        }]
+    });
+    let composition =
+        Composition::from_bytes(serde_json::to_vec(&lottie).unwrap()).unwrap();
+    let mut sink = Ops::default();
+    Renderer::new().render_frame(
+        &composition,
+        0.0,
+        Affine::IDENTITY,
+        1.0,
+        &mut sink,
+    );
+    sink.0
+}
+
+/// 10x10 square at the origin.
+fn square() -> serde_json::Value {
+    json!({
+        "ty": "rc",
+        "p": static_value(json!([5, 5])),
+        "s": static_value(json!([10, 10])),
+        "r": static_value(json!(0))
+    })
+}
+
+fn fill(bm: u8) -> serde_json::Value {
+    json!({
+        "ty": "fl",
+        "c": static_value(json!([1, 0, 0])),
+        "o": static_value(json!(100)),
+        "bm": bm
+    })
+}
+
+fn stroke(bm: u8) -> serde_json::Value {
+    json!({
+        "ty": "st",
+        "c": static_value(json!([0, 0, 1])),
+        "o": static_value(json!(100)),
+        "w": static_value(json!(2)),
+        "lj": 2,
+        "bm": bm
+    })
+}
+
+#[test]
+fn test_shape_blend_mode() {
+    // Fill with its own blend mode.
+    assert_eq!(
+        render_shapes(json!([square(), fill(2)])),
+        [
+            Op::Push(
+                BlendMode::from(Mix::Screen),
+                Rect::new(0.0, 0.0, 10.0, 10.0)
+            ),
+            Op::Fill,
+            Op::Pop
+        ]
+    );
+    // Normal blend mode draws directly.
+    assert_eq!(render_shapes(json!([square(), fill(0)])), [Op::Fill]);
+}
+
+#[test]
+fn test_group_blend_mode() {
+    // Group blend mode composites the draws of the group as a single unit.
+    let ops = render_shapes(json!([{
+        "ty": "gr",
+        "bm": 3,
+        "it": [square(), fill(0), stroke(1)]
+    }]));
+    let stroke_bounds = Rect::new(-2.0, -2.0, 12.0, 12.0);
+    assert_eq!(
+        ops,
+        [
+            Op::Push(BlendMode::from(Mix::Overlay), stroke_bounds),
+            Op::Push(BlendMode::from(Mix::Multiply), stroke_bounds),
+            Op::Stroke,
+            Op::Pop,
+            Op::Fill,
+            Op::Pop
+        ]
+    );
+
+    // Each copy of a repeated group is composited on its own.
+    let ops = render_shapes(json!([
+        { "ty": "gr", "bm": 3, "it": [square(), fill(0)] },
+        {
+            "ty": "rp",
+            "c": static_value(json!(3)),
+            "o": static_value(json!(0)),
+            "m": 1,
+            "tr": {
+                "a": static_value(json!([0, 0])),
+                "p": static_value(json!([20, 0])),
+                "s": static_value(json!([100, 100])),
+                "r": static_value(json!(0)),
+                "so": static_value(json!(100)),
+                "eo": static_value(json!(100))
+            }
+        }
+    ]));
+    let copies = [0.0, 20.0, 40.0].map(|x| {
+        let bounds = Rect::new(x, 0.0, x + 10.0, 10.0);
+        [
+            Op::Push(BlendMode::from(Mix::Overlay), bounds),
+            Op::Fill,
+            Op::Pop,
+        ]
+    });
+    assert_eq!(ops, copies.into_iter().flatten().collect::<Vec<_>>());
+}
@@ -0,0 +1,172 @@
+use serde_json::json;
+use vellottie::parser::{breadcrumb::Breadcrumb, schema::shapes::AnyShape};
+use vellottie::runtime::{
+    model::fixed,
+    vello::{
+        kurbo::{Affine, BezPath, ParamCurve, PathEl, Shape},
+        peniko::{BlendMode, Fill},
+    },
+    Composition, RenderSink, Renderer,
+};
+
+/// Render sink that records the fill rule and path of each fill.
+#[derive(Default)]
+struct Fills(Vec<(Fill, BezPath)>);
+
+impl RenderSink for Fills {
+    fn push_layer(
+        &mut self,
+        _blend: impl Into<BlendMode>,
+        _alpha: f32,
+        _transform: Affine,
+        _shape: &impl Shape,
+    ) {
+    }
+
+    fn pop_layer(&mut self) {}
+
+    fn draw(
+        &mut self,
+        _stroke: Option<&fixed::Stroke>,
+        fill: Fill,
+        transform: Affine,
+        _brush: &fixed::Brush,
+        shape: &impl Shape,
+    ) {
+        self.0.push((fill, transform * shape.to_path(0.1)));
+    }
+}
+
+fn static_value(value: serde_json::Value) -> serde_json::Value {
+    json!({ "a": 0, "k": value })
+}
+
+fn assert_parses(json: serde_json::Value) {
+    let shape = AnyShape::from_json(&mut Breadcrumb::new(), &json).unwrap();
+    assert_eq!(serde_json::from_value::<AnyShape>(json).unwrap(), shape);
+}
+
+/// Fill rule and path of the single fill drawn for `shapes`.
+fn render_fill(shapes: serde_json::Value) -> (Fill, BezPath) {
+    let lottie = json!({
+        "v": "5.5.2",
+        "fr": 10,
+        "ip": 0,
+        "op": 20,
+        "w": 100,
+        "h": 100,
+        "layers": [{
+            "ty": 4,
+            "ind": 1,
+            "st": 0,
+            "ip": 0,
+            "op": 20,
+            "ks": {
+                "a": static_value(json!([0, 0])),
+                "p": static_value(json!([0, 0])),
+                "s": static_value(json!([100, 100])),
+                "r": static_value(json!(0)),
+                "o": static_value(json!(100))
+            },
+            "shapes": shapes
+        }]
+    });
+    let composition =
+        Composition::from_bytes(serde_json::to_vec(&lottie).unwrap()).unwrap();
+    let mut sink = Fills::default();
+    Renderer::new().render_frame(
+        &composition,
+        0.0,
+        Affine::IDENTITY,
+        1.0,
+        &mut sink,
+    );
+    match sink.0.pop() {
+        Some(fill) if sink.0.is_empty() => fill,
+        _ => panic!("expected a single fill"),
+    }
+}
+
+fn rect(direction: u8, size: f64) -> serde_json::Value {
+    json!({
+        "ty": "rc",
+        "d": direction,
+        "p": static_value(json!([5, 5])),
+        "s": static_value(json!([size, size])),
+        "r": static_value(json!(0))
+    })
+}
+
+fn ellipse(direction: u8) -> serde_json::Value {
+    json!({
+        "ty": "el",
+        "d": direction,
+        "p": static_value(json!([5, 5])),
+        "s": static_value(json!([10, 10]))
+    })
+}
+
+fn fill(fill_rule: u8) -> serde_json::Value {
+    json!({
+        "ty": "fl",
+        "c": static_value(json!([1, 0, 0])),
+        "o": static_value(json!(100)),
+        "r": fill_rule
+    })
+}
+
+#[test]
+fn test_shape_direction() {
+    let area = |shape| render_fill(json!([shape, fill(1)])).1.area();
+    assert_parses(rect(3, 10.0));
+    assert_parses(ellipse(3));
+    // Reversed shapes cover the same area with the opposite winding.
+    assert_eq!(area(rect(3, 10.0)), -area(rect(1, 10.0)));
+    assert!((area(rect(1, 10.0)).abs() - 100.0).abs() < 1e-9);
+    assert!((area(ellipse(3)) + area(ellipse(1))).abs() < 1e-9);
+    assert!(area(ellipse(1)).abs() > 75.0);
+}
+
+#[test]
+fn test_fill_rule() {
+    // A smaller square in the same direction inside a larger one, filled as
+    // a single path.
+    let fill_rule = |fill_rule| {
+        let shapes = json!([rect(1, 10.0), rect(1, 4.0), fill(fill_rule)]);
+        let (fill, path) = render_fill(shapes);
+        assert_eq!(path.segments().count(), 16);
+        fill
+    };
+    assert_eq!(fill_rule(1), Fill::NonZero);
+    assert_eq!(fill_rule(2), Fill::EvenOdd);
+}
+
+#[test]
+fn test_trimmed_shape_direction() {
+    // Filled first quarter of a shape.
+    let quarter = |shape| {
+        let trim = json!({
+            "ty": "tm",
+            "s": static_value(json!(0)),
+            "e": static_value(json!(25)),
+            "o": static_value(json!(0)),
+            "m": 1
+        });
+        render_fill(json!([shape, trim, fill(1)])).1
+    };
+    // Reversed rectangles start at the top right corner and run to the
+    // left.
+    assert_eq!(
+        quarter(rect(3, 10.0)).elements(),
+        [
+            PathEl::MoveTo((10.0, 0.0).into()),
+            PathEl::LineTo((0.0, 0.0).into())
+        ]
+    );
+    // Reversed ellipses start at the top and run to the left.
+    let path = quarter(ellipse(3));
+    let start = path.segments().next().unwrap().start();
+    let end = path.segments().last().unwrap().end();
+    assert!(start.distance((5.0, 0.0).into()) < 1e-6, "{start:?}");
+    assert!(end.distance((0.0, 5.0).into()) < 1e-3, "{end:?}");
+}
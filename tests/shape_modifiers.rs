@@ -0,0 +1,246 @@
+use serde_json::json;
+use vellottie::parser::{breadcrumb::Breadcrumb, schema::shapes::AnyShape};
+use vellottie::runtime::{
+    model::fixed,
+    vello::{
+        kurbo::{Affine, BezPath, PathEl, Rect, Shape},
+        peniko::{BlendMode, Fill},
+    },
+    Composition, RenderSink, Renderer,
+};
+
+/// Render sink that records the path of each draw.
+#[derive(Default)]
+struct Paths(Vec<BezPath>);
+
+impl RenderSink for Paths {
+    fn push_layer(
+        &mut self,
+        _blend: impl Into<BlendMode>,
+        _alpha: f32,
+        _transform: Affine,
+        _shape: &impl Shape,
+    ) {
+    }
+
+    fn pop_layer(&mut self) {}
+
+    fn draw(
+        &mut self,
+        _stroke: Option<&fixed::Stroke>,
+        _fill: Fill,
+        transform: Affine,
+        _brush: &fixed::Brush,
+        shape: &impl Shape,
+    ) {
+        self.0.push(transform * shape.to_path(0.1));
+    }
+}
+
+fn static_value(value: serde_json::Value) -> serde_json::Value {
+    json!({ "a": 0, "k": value })
+}
+
+fn assert_parses(json: serde_json::Value) {
+    let shape = AnyShape::from_json(&mut Breadcrumb::new(), &json).unwrap();
+    assert_eq!(serde_json::from_value::<AnyShape>(json).unwrap(), shape);
+}
+
+/// Composition with a filled `shape` with `modifier` applied.
+fn import_modified(
+    shape: serde_json::Value,
+    modifier: serde_json::Value,
+) -> Composition {
+    let lottie = json!({
+        "v": "5.5.2",
+        "fr": 10,
+        "ip": 0,
+        "op": 20,
+        "w": 100,
+        "h": 100,
+        "layers": [{
+            "ty": 4,
+            "ind": 1,
+            "st": 0,
+            "ip": 0,
+            "op": 20,
+            "ks": {
+                "a": static_value(json!([0, 0])),
+                "p": static_value(json!([0, 0])),
+                "s": static_value(json!([100, 100])),
+                "r": static_value(json!(0)),
+                "o": static_value(json!(100))
+            },
+            "shapes": [
+                shape,
+                modifier,
+                {
+                    "ty": "fl",
+                    "c": static_value(json!([1, 0, 0])),
+                    "o": static_value(json!(100))
+                }
+            ]
+        }]
+    });
+    Composition::from_bytes(serde_json::to_vec(&lottie).unwrap()).unwrap()
+}
+
+/// Composition with a filled 10x10 square with `modifier` applied.
+fn import_modified_square(modifier: serde_json::Value) -> Composition {
+    let square = json!({
+        "ty": "rc",
+        "p": static_value(json!([5, 5])),
+        "s": static_value(json!([10, 10])),
+        "r": static_value(json!(0))
+    });
+    import_modified(square, modifier)
+}
+
+fn render_path(composition: &Composition, frame: f32) -> BezPath {
+    let mut sink = Paths::default();
+    Renderer::new().render_frame(
+        composition,
+        frame,
+        Affine::IDENTITY,
+        1.0,
+        &mut sink,
+    );
+    match &sink.0[..] {
+        [path] => path.clone(),
+        _ => panic!("expected a single draw"),
+    }
+}
+
+fn modified_square(modifier: serde_json::Value) -> BezPath {
+    render_path(&import_modified_square(modifier), 0.0)
+}
+
+fn zig_zag(point_type: serde_json::Value) -> serde_json::Value {
+    json!({
+        "ty": "zz",
+        "r": static_value(json!(1)),
+        "s": static_value(json!(2)),
+        "pt": point_type
+    })
+}
+
+#[test]
+fn test_zig_zag() {
+    assert_parses(zig_zag(static_value(json!(1))));
+    // Vertices move inward along the diagonals and the midpoints of the
+    // edges move outward.
+    let path = modified_square(zig_zag(static_value(json!(1))));
+    let elements = path.elements();
+    assert_eq!(elements.len(), 10);
+    assert!(elements[1..9]
+        .iter()
+        .all(|el| matches!(el, PathEl::LineTo(_))));
+    assert_eq!(elements[1], PathEl::LineTo((5.0, -2.0).into()));
+    assert_eq!(path.bounding_box(), Rect::new(-2.0, -2.0, 12.0, 12.0));
+    // Smooth points are joined by curves.
+    let path = modified_square(zig_zag(static_value(json!(2))));
+    let elements = path.elements();
+    assert_eq!(elements.len(), 10);
+    assert!(elements[1..9]
+        .iter()
+        .all(|el| matches!(el, PathEl::CurveTo(..))));
+}
+
+#[test]
+fn test_animated_zig_zag_point_type() {
+    // The point type switches to smooth points at frame 10.
+    let point_type = json!({
+        "a": 1,
+        "k": [{ "t": 0, "s": [1], "h": 1 }, { "t": 10, "s": [2] }]
+    });
+    let composition = import_modified_square(zig_zag(point_type));
+    let is_smooth = |frame| {
+        let path = render_path(&composition, frame);
+        matches!(path.elements()[1], PathEl::CurveTo(..))
+    };
+    assert!(!is_smooth(0.0));
+    assert!(is_smooth(10.0));
+}
+
+#[test]
+fn test_twist() {
+    let twist = |angle: f32| {
+        json!({
+            "ty": "tw",
+            "a": static_value(json!(angle)),
+            "c": static_value(json!([5, 5]))
+        })
+    };
+    assert_parses(twist(90.0));
+    // The corners are farthest from the center and stay in place while the
+    // edges bend around it.
+    let path = modified_square(twist(90.0));
+    assert_eq!(path.elements()[0], PathEl::MoveTo((0.0, 0.0).into()));
+    let bends = path.elements().iter().any(|el| match el {
+        PathEl::CurveTo(p1, _, p3) => p3.y == 0.0 && p1.y.abs() > 0.1,
+        _ => false,
+    });
+    assert!(bends);
+    let bounds = path.bounding_box();
+    assert_eq!(bounds.union(Rect::new(0.0, 0.0, 10.0, 10.0)), bounds);
+    // No angle leaves the square as it is.
+    assert_eq!(
+        modified_square(twist(0.0)).bounding_box(),
+        Rect::new(0.0, 0.0, 10.0, 10.0)
+    );
+}
+
+#[test]
+fn test_rounded_corners() {
+    let rounded_corners = |radius: f32| {
+        json!({
+            "ty": "rd",
+            "r": static_value(json!(radius))
+        })
+    };
+    assert_parses(rounded_corners(2.0));
+    let path = modified_square(rounded_corners(2.0));
+    let elements = path.elements();
+    assert_eq!(elements[0], PathEl::MoveTo((2.0, 0.0).into()));
+    assert_eq!(elements[1], PathEl::LineTo((8.0, 0.0).into()));
+    assert_eq!(elements.len(), 10);
+    assert_eq!(path.bounding_box(), Rect::new(0.0, 0.0, 10.0, 10.0));
+    let area = 100.0 - 4.0 * (4.0 - std::f64::consts::PI);
+    assert!((path.area().abs() - area).abs() < 0.01);
+    // Radii are limited to half the length of the edges.
+    let path = modified_square(rounded_corners(20.0));
+    let area = std::f64::consts::PI * 25.0;
+    assert!((path.area().abs() - area).abs() < 0.1);
+}
+
+#[test]
+fn test_rounded_corners_curve() {
+    // Triangle with a curved edge that leaves the corner at (10, 0) without
+    // a handle and bulges out before reaching (10, 10).
+    let triangle = json!({
+        "ty": "sh",
+        "ks": static_value(json!({
+            "c": true,
+            "v": [[0, 0], [10, 0], [10, 10]],
+            "i": [[0, 0], [0, 0], [5, 0]],
+            "o": [[0, 0], [0, 0], [0, 0]]
+        }))
+    });
+    let rounded_corners = json!({
+        "ty": "rd",
+        "r": static_value(json!(2))
+    });
+    let path = render_path(&import_modified(triangle, rounded_corners), 0.0);
+    let vertices = path
+        .elements()
+        .iter()
+        .filter_map(|el| el.end_point())
+        .collect::<Vec<_>>();
+    // The corners next to the straight edges are rounded, including the one
+    // joining the curve, while the one with a handle is kept.
+    assert!(!vertices.contains(&(0.0, 0.0).into()));
+    assert!(!vertices.contains(&(10.0, 0.0).into()));
+    assert!(vertices.contains(&(10.0, 10.0).into()));
+    assert!(vertices.contains(&(2.0, 0.0).into()));
+    assert!(vertices.contains(&(8.0, 0.0).into()));
+}
@@ -0,0 +1,143 @@
+use serde_json::json;
+use vellottie::runtime::{
+    model::fixed,
+    vello::{
+        kurbo::{Affine, Rect, Shape},
+        peniko::{BlendMode, Fill},
+    },
+    Composition, RenderSink, Renderer,
+};
+
+/// Render sink that records the color and bounds of each solid fill.
+#[derive(Default)]
+struct Fills(Vec<(fixed::Color, Rect)>);
+
+impl RenderSink for Fills {
+    fn push_layer(
+        &mut self,
+        _blend: impl Into<BlendMode>,
+        _alpha: f32,
+        _transform: Affine,
+        _shape: &impl Shape,
+    ) {
+    }
+
+    fn pop_layer(&mut self) {}
+
+    fn draw(
+        &mut self,
+        stroke: Option<&fixed::Stroke>,
+        _fill: Fill,
+        transform: Affine,
+        brush: &fixed::Brush,
+        shape: &impl Shape,
+    ) {
+        let (None, fixed::Brush::Solid(color)) = (stroke, brush) else {
+            panic!("expected a solid fill");
+        };
+        let bounds = transform.transform_rect_bbox(shape.bounding_box());
+        self.0.push((*color, bounds));
+    }
+}
+
+fn static_value(value: serde_json::Value) -> serde_json::Value {
+    json!({ "a": 0, "k": value })
+}
+
+fn transform() -> serde_json::Value {
+    json!({
+        "a": static_value(json!([0, 0])),
+        "p": static_value(json!([0, 0])),
+        "s": static_value(json!([100, 100])),
+        "r": static_value(json!(0)),
+        "o": static_value(json!(100))
+    })
+}
+
+/// 40x30 solid layer of the given color.
+fn solid_layer(color: &str) -> serde_json::Value {
+    json!({
+        "ty": 1,
+        "ind": 2,
+        "st": 0,
+        "ip": 0,
+        "op": 20,
+        "ks": transform(),
+        "sc": color,
+        "sw": 40,
+        "sh": 30
+    })
+}
+
+fn render_fills(layers: serde_json::Value) -> Vec<(fixed::Color, Rect)> {
+    let lottie = json!({
+        "v": "5.5.2",
+        "fr": 10,
+        "ip": 0,
+        "op": 20,
+        "w": 100,
+        "h": 100,
+        "layers": layers
+    });
+    let composition =
+        Composition::from_bytes(serde_json::to_vec(&lottie).unwrap()).unwrap();
+    let mut sink = Fills::default();
+    Renderer::new().render_frame(
+        &composition,
+        0.0,
+        Affine::IDENTITY,
+        1.0,
+        &mut sink,
+    );
+    sink.0
+}
+
+/// Fill of the full 40x30 solid layer.
+fn solid_fill(r: u8, g: u8, b: u8) -> (fixed::Color, Rect) {
+    (fixed::Color::rgb8(r, g, b), Rect::new(0.0, 0.0, 40.0, 30.0))
+}
+
+#[test]
+fn test_solid_color() {
+    let fills = render_fills(json!([solid_layer("#ff8000")]));
+    assert_eq!(fills, [solid_fill(0xff, 0x80, 0x00)]);
+}
+
+#[test]
+fn test_solid_color_matte() {
+    let mut matte = solid_layer("#00ff00");
+    matte["td"] = json!(1);
+    let square = json!({
+        "ty": 4,
+        "ind": 1,
+        "st": 0,
+        "ip": 0,
+        "op": 20,
+        "tt": 1,
+        "ks": transform(),
+        "shapes": [
+            {
+                "ty": "rc",
+                "p": static_value(json!([5, 5])),
+                "s": static_value(json!([10, 10])),
+                "r": static_value(json!(0))
+            },
+            {
+                "ty": "fl",
+                "c": static_value(json!([1, 0, 0])),
+                "o": static_value(json!(100))
+            }
+        ]
+    });
+    // The solid is drawn as the matte of the square.
+    let fills = render_fills(json!([matte, square]));
+    assert_eq!(fills.len(), 2);
+    assert!(fills.contains(&solid_fill(0x00, 0xff, 0x00)));
+}
+
+#[test]
+fn test_invalid_solid_color() {
+    // Colors that fail to parse are drawn black.
+    let fills = render_fills(json!([solid_layer("orange")]));
+    assert_eq!(fills, [solid_fill(0x00, 0x00, 0x00)]);
+}
@@ -0,0 +1,199 @@
+use serde_json::json;
+use vellottie::runtime::{
+    model::{fixed, Content, Shape, Stroke},
+    vello::{
+        kurbo::{self, Affine, BezPath, ParamCurveArclen, PathEl},
+        peniko::{BlendMode, Fill},
+    },
+    Composition, RenderSink, Renderer,
+};
+
+fn import_stroke(dashes: serde_json::Value) -> Stroke {
+    let lottie = json!({
+        "v": "5.5.2",
+        "fr": 10,
+        "ip": 0,
+        "op": 20,
+        "w": 100,
+        "h": 100,
+        "layers": [{
+            "ty": 4,
+            "ind": 1,
+            "st": 0,
+            "ip": 0,
+            "op": 20,
+            "ks": {
+                "a": { "a": 0, "k": [0, 0] },
+                "p": { "a": 0, "k": [0, 0] },
+                "s": { "a": 0, "k": [100, 100] },
+                "r": { "a": 0, "k": 0 },
+                "o": { "a": 0, "k": 100 }
+            },
+            "shapes": [{
+                "ty": "st",
+                "c": { "a": 0, "k": [0, 0, 0] },
+                "o": { "a": 0, "k": 100 },
+                "w": { "a": 0, "k": 2 },
+                "d": dashes
+            }]
+        }]
+    });
+    let composition =
+        Composition::from_bytes(serde_json::to_vec(&lottie).unwrap()).unwrap();
+    let Content::Shape(shapes) = &composition.layers[0].content else {
+        panic!("expected shape layer");
+    };
+    let Some(Shape::Draw(draw)) = shapes.first() else {
+        panic!("expected draw");
+    };
+    draw.stroke.clone().expect("expected stroke")
+}
+
+fn dash(dash_type: &str, length: serde_json::Value) -> serde_json::Value {
+    json!({ "n": dash_type, "nm": dash_type, "v": length })
+}
+
+#[test]
+fn test_static_dashes() {
+    let stroke = import_stroke(json!([
+        dash("d", json!({ "a": 0, "k": 4 })),
+        dash("g", json!({ "a": 0, "k": 2 })),
+        dash("o", json!({ "a": 0, "k": 1 }))
+    ]));
+    assert!(stroke.is_fixed());
+    let stroke = stroke.evaluate(0.0).to_owned();
+    assert_eq!(stroke.dash_pattern.as_slice(), &[4.0, 2.0]);
+    assert_eq!(stroke.dash_offset, 1.0);
+}
+
+#[test]
+fn test_animated_dash_offset() {
+    let stroke = import_stroke(json!([
+        dash("d", json!({ "a": 0, "k": 3 })),
+        dash(
+            "o",
+            json!({
+                "a": 1,
+                "k": [
+                    { "t": 0, "s": [0], "i": { "x": [1], "y": [1] }, "o": { "x": [0], "y": [0] } },
+                    { "t": 10, "s": [10] }
+                ]
+            })
+        )
+    ]));
+    assert!(!stroke.is_fixed());
+    let stroke = stroke.evaluate(5.0).to_owned();
+    // Odd patterns are repeated so that dashes and gaps alternate.
+    assert_eq!(stroke.dash_pattern.as_slice(), &[3.0, 3.0]);
+    assert_eq!(stroke.dash_offset, 5.0);
+}
+
+#[test]
+fn test_empty_dashes() {
+    let stroke = import_stroke(json!([
+        dash("d", json!({ "a": 0, "k": 0 })),
+        dash("g", json!({ "a": 0, "k": 0 }))
+    ]))
+    .evaluate(0.0)
+    .to_owned();
+    assert!(stroke.dash_pattern.is_empty());
+}
+
+/// Render sink that records the dash patterns and paths of strokes.
+#[derive(Default)]
+struct Strokes(Vec<(Vec<f64>, BezPath)>);
+
+impl RenderSink for Strokes {
+    fn push_layer(
+        &mut self,
+        _blend: impl Into<BlendMode>,
+        _alpha: f32,
+        _transform: Affine,
+        _shape: &impl kurbo::Shape,
+    ) {
+    }
+
+    fn pop_layer(&mut self) {}
+
+    fn draw(
+        &mut self,
+        stroke: Option<&fixed::Stroke>,
+        _fill: Fill,
+        _transform: Affine,
+        _brush: &fixed::Brush,
+        shape: &impl kurbo::Shape,
+    ) {
+        let stroke = stroke.expect("expected stroke");
+        self.0
+            .push((stroke.dash_pattern.to_vec(), shape.to_path(0.1)));
+    }
+}
+
+#[test]
+fn test_render_dashes() {
+    let static_value = |value| json!({ "a": 0, "k": value });
+    let lottie = json!({
+        "v": "5.5.2",
+        "fr": 10,
+        "ip": 0,
+        "op": 20,
+        "w": 100,
+        "h": 100,
+        "layers": [{
+            "ty": 4,
+            "ind": 1,
+            "st": 0,
+            "ip": 0,
+            "op": 20,
+            "ks": {
+                "a": static_value(json!([0, 0])),
+                "p": static_value(json!([0, 0])),
+                "s": static_value(json!([100, 100])),
+                "r": static_value(json!(0)),
+                "o": static_value(json!(100))
+            },
+            "shapes": [
+                {
+                    "ty": "rc",
+                    "p": static_value(json!([5, 5])),
+                    "s": static_value(json!([10, 10])),
+                    "r": static_value(json!(0))
+                },
+                {
+                    "ty": "st",
+                    "c": static_value(json!([0, 0, 0])),
+                    "o": static_value(json!(100)),
+                    "w": static_value(json!(2)),
+                    "d": [
+                        dash("d", static_value(json!(4))),
+                        dash("g", static_value(json!(6)))
+                    ]
+                }
+            ]
+        }]
+    });
+    let composition =
+        Composition::from_bytes(serde_json::to_vec(&lottie).unwrap()).unwrap();
+    let mut sink = Strokes::default();
+    Renderer::new().render_frame(
+        &composition,
+        0.0,
+        Affine::IDENTITY,
+        1.0,
+        &mut sink,
+    );
+    let [(dash_pattern, path)] = sink.0.as_slice() else {
+        panic!("expected a single stroke");
+    };
+    // The sink receives the dashes as a path to be stroked solid.
+    assert!(dash_pattern.is_empty());
+    let dash_count = path
+        .elements()
+        .iter()
+        .filter(|el| matches!(el, PathEl::MoveTo(_)))
+        .count();
+    assert!(dash_count >= 4);
+    // Four dashes of length 4 along the perimeter of 40.
+    let length = path.segments().map(|seg| seg.arclen(1e-6)).sum::<f64>();
+    assert!((length - 16.0).abs() < 1e-3);
+}
@@ -0,0 +1,150 @@
+use serde_json::json;
+use vellottie::runtime::{
+    model::fixed,
+    vello::{
+        kurbo::{Affine, Shape},
+        peniko::{BlendMode, Fill},
+    },
+    Composition, RenderSink, Renderer,
+};
+
+/// Render sink that only counts draws.
+#[derive(Default)]
+struct DrawCount(usize);
+
+impl RenderSink for DrawCount {
+    fn push_layer(
+        &mut self,
+        _blend: impl Into<BlendMode>,
+        _alpha: f32,
+        _transform: Affine,
+        _shape: &impl Shape,
+    ) {
+    }
+
+    fn pop_layer(&mut self) {}
+
+    fn draw(
+        &mut self,
+        _stroke: Option<&fixed::Stroke>,
+        _fill: Fill,
+        _transform: Affine,
+        _brush: &fixed::Brush,
+        _shape: &impl Shape,
+    ) {
+        self.0 += 1;
+    }
+}
+
+fn draw_count(composition: &Composition, frame: f32) -> usize {
+    let mut sink = DrawCount::default();
+    Renderer::new().render_frame(
+        composition,
+        frame,
+        Affine::IDENTITY,
+        1.0,
+        &mut sink,
+    );
+    sink.0
+}
+
+fn static_value(value: serde_json::Value) -> serde_json::Value {
+    json!({ "a": 0, "k": value })
+}
+
+/// Composition with a precomposition layer starting at `start` and remapped
+/// by `time_remap`. The asset shows a square on its frame 5 only.
+fn import_precomp(
+    start: f32,
+    time_remap: Option<serde_json::Value>,
+) -> Composition {
+    let transform = json!({
+        "a": static_value(json!([0, 0])),
+        "p": static_value(json!([0, 0])),
+        "s": static_value(json!([100, 100])),
+        "r": static_value(json!(0)),
+        "o": static_value(json!(100))
+    });
+    let square = json!({
+        "ty": 4,
+        "ind": 1,
+        "st": 0,
+        "ip": 5,
+        "op": 6,
+        "ks": transform,
+        "shapes": [
+            {
+                "ty": "rc",
+                "p": static_value(json!([5, 5])),
+                "s": static_value(json!([10, 10])),
+                "r": static_value(json!(0))
+            },
+            {
+                "ty": "fl",
+                "c": static_value(json!([1, 0, 0])),
+                "o": static_value(json!(100))
+            }
+        ]
+    });
+    let mut layer = json!({
+        "ty": 0,
+        "ind": 1,
+        "st": start,
+        "ip": 0,
+        "op": 20,
+        "w": 100,
+        "h": 100,
+        "refId": "square",
+        "ks": transform
+    });
+    if let Some(time_remap) = time_remap {
+        layer["tm"] = time_remap;
+    }
+    let lottie = json!({
+        "v": "5.5.2",
+        "fr": 10,
+        "ip": 0,
+        "op": 20,
+        "w": 100,
+        "h": 100,
+        "assets": [{ "id": "square", "layers": [square] }],
+        "layers": [layer]
+    });
+    Composition::from_bytes(serde_json::to_vec(&lottie).unwrap()).unwrap()
+}
+
+#[test]
+fn test_time_remap() {
+    // Without remapping, the square is only visible at frame 5.
+    let composition = import_precomp(0.0, None);
+    assert_eq!(draw_count(&composition, 0.0), 0);
+    assert_eq!(draw_count(&composition, 5.0), 1);
+
+    // Remapping to 0.5 seconds at 10 fps freezes the precomp on frame 5.
+    let composition = import_precomp(0.0, Some(static_value(json!(0.5))));
+    assert_eq!(draw_count(&composition, 0.0), 1);
+    assert_eq!(draw_count(&composition, 12.0), 1);
+}
+
+#[test]
+fn test_time_remap_start_time() {
+    // Linear remap from 0 to 1 second over the first 10 frames of the layer.
+    let time_remap = json!({
+        "a": 1,
+        "k": [
+            {
+                "t": 0,
+                "s": [0],
+                "i": { "x": [1], "y": [1] },
+                "o": { "x": [0], "y": [0] }
+            },
+            { "t": 10, "s": [1] }
+        ]
+    });
+    let composition = import_precomp(10.0, Some(time_remap));
+    // The remap is keyed in layer time, so frame 5 of the asset is shown at
+    // 10 + 5 = 15.
+    assert_eq!(draw_count(&composition, 5.0), 0);
+    assert_eq!(draw_count(&composition, 15.0), 1);
+    assert_eq!(draw_count(&composition, 17.0), 0);
+}
@@ -0,0 +1,128 @@
+use serde_json::json;
+use vellottie::runtime::{
+    model::fixed,
+    vello::{
+        kurbo::{Affine, Shape},
+        peniko::{BlendMode, Fill},
+    },
+    Composition, RenderSink, Renderer,
+};
+
+/// Render sink that records whether anything was drawn.
+#[derive(Default)]
+struct Visibility {
+    is_visible: bool,
+}
+
+impl RenderSink for Visibility {
+    fn push_layer(
+        &mut self,
+        _blend: impl Into<BlendMode>,
+        _alpha: f32,
+        _transform: Affine,
+        _shape: &impl Shape,
+    ) {
+    }
+
+    fn pop_layer(&mut self) {}
+
+    fn draw(
+        &mut self,
+        _stroke: Option<&fixed::Stroke>,
+        _fill: Fill,
+        _transform: Affine,
+        _brush: &fixed::Brush,
+        _shape: &impl Shape,
+    ) {
+        self.is_visible = true;
+    }
+}
+
+fn is_visible(composition: &Composition, frame: f32) -> bool {
+    let mut sink = Visibility::default();
+    Renderer::new().render_frame(
+        composition,
+        frame,
+        Affine::IDENTITY,
+        1.0,
+        &mut sink,
+    );
+    sink.is_visible
+}
+
+/// Composition instancing an asset that shows a circle on its frame 5 only.
+/// The properties of `layer` are set on the precomposition layer.
+fn import_precomp(layer: serde_json::Value) -> Composition {
+    let static_value = |value| json!({ "a": 0, "k": value });
+    let transform = json!({
+        "a": static_value(json!([0, 0])),
+        "p": static_value(json!([0, 0])),
+        "s": static_value(json!([100, 100])),
+        "r": static_value(json!(0)),
+        "o": static_value(json!(100))
+    });
+    let circle = json!({
+        "ty": 4,
+        "ind": 1,
+        "st": 0,
+        "ip": 5,
+        "op": 6,
+        "ks": transform,
+        "shapes": [
+            {
+                "ty": "el",
+                "p": static_value(json!([5, 5])),
+                "s": static_value(json!([10, 10]))
+            },
+            {
+                "ty": "fl",
+                "c": static_value(json!([1, 0, 0])),
+                "o": static_value(json!(100))
+            }
+        ]
+    });
+    let mut precomp = json!({
+        "ty": 0,
+        "ind": 1,
+        "st": 0,
+        "ip": 0,
+        "op": 20,
+        "w": 100,
+        "h": 100,
+        "refId": "circle",
+        "ks": transform
+    });
+    for (key, value) in layer.as_object().unwrap() {
+        precomp[key] = value.clone();
+    }
+    let lottie = json!({
+        "v": "5.5.2",
+        "fr": 10,
+        "ip": 0,
+        "op": 20,
+        "w": 100,
+        "h": 100,
+        "assets": [{ "id": "circle", "layers": [circle] }],
+        "layers": [precomp]
+    });
+    Composition::from_bytes(serde_json::to_vec(&lottie).unwrap()).unwrap()
+}
+
+#[test]
+fn test_time_stretch() {
+    let composition = import_precomp(json!({ "st": 2, "sr": 2 }));
+    // Frame 5 of the asset is shown at 2 + 5 * 2 = 12.
+    assert!(!is_visible(&composition, 7.0));
+    assert!(is_visible(&composition, 12.0));
+    assert!(is_visible(&composition, 13.0));
+    assert!(!is_visible(&composition, 14.0));
+}
+
+#[test]
+fn test_default_time_stretch() {
+    // Without a stretch, the asset plays at the rate of the layer.
+    let composition = import_precomp(json!({ "st": 2 }));
+    assert!(!is_visible(&composition, 5.0));
+    assert!(is_visible(&composition, 7.0));
+    assert!(!is_visible(&composition, 8.0));
+}
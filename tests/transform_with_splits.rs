@@ -2,9 +2,10 @@ use lazy_static::lazy_static;
 use serde_json::json;
 use vellottie::parser::{
     breadcrumb::{Breadcrumb, ValueType},
-    schema::helpers::transform::Transform,
+    schema::helpers::transform::{AnyTransformR, Transform},
     *,
 };
+use vellottie::runtime::vello::kurbo::Point;
 
 #[test]
 fn test_deserialize() {
@@ -23,6 +24,119 @@ fn test_deserialize() {
     }
 }
 
+#[test]
+fn test_split_rotation() {
+    let json = json!(
+        {
+            "p": { "a": 0, "k": [0, 0, 0] },
+            "rx": { "a": 0, "k": 10 },
+            "ry": { "a": 0, "k": 20 },
+            "rz": { "a": 0, "k": 30 },
+            "or": { "a": 0, "k": [0, 0, 0] }
+        }
+    );
+    let serde_transform: Transform =
+        serde_json::from_value(json.clone()).unwrap();
+    assert!(matches!(
+        serde_transform.rotation,
+        Some(AnyTransformR::SplitRotation { .. })
+    ));
+    let transform =
+        Transform::from_obj(&mut Breadcrumb::new(), json.as_object().unwrap())
+            .unwrap();
+    assert_eq!(serde_transform, transform);
+    assert_eq!(serde_json::to_value(&transform).unwrap(), json);
+}
+
+#[test]
+fn test_partial_split_rotation() {
+    let json = json!(
+        {
+            "p": { "a": 0, "k": [0, 0, 0] },
+            "rz": { "a": 0, "k": 30 }
+        }
+    );
+    let serde_transform: Transform =
+        serde_json::from_value(json.clone()).unwrap();
+    assert!(matches!(
+        serde_transform.rotation,
+        Some(AnyTransformR::SplitRotation {
+            x_rotation: None,
+            z_rotation: Some(_),
+            ..
+        })
+    ));
+    let transform =
+        Transform::from_obj(&mut Breadcrumb::new(), json.as_object().unwrap())
+            .unwrap();
+    assert_eq!(serde_transform, transform);
+    assert_eq!(serde_json::to_value(&transform).unwrap(), json);
+}
+
+#[test]
+fn test_import_split_transform() {
+    let transform = json!(
+        {
+            "p": {
+                "s": true,
+                "x": { "a": 0, "k": 10 },
+                "y": { "a": 0, "k": 20 }
+            },
+            "s": { "a": 0, "k": [100, 100, 100] },
+            "rx": { "a": 0, "k": 0 },
+            "ry": { "a": 0, "k": 0 },
+            "rz": { "a": 0, "k": 90 },
+            "or": { "a": 0, "k": [0, 0, 0] }
+        }
+    );
+    let mut group_transform = transform.clone();
+    group_transform["ty"] = json!("tr");
+    let lottie = json!(
+        {
+            "v": "5.5.2",
+            "fr": 60,
+            "ip": 0,
+            "op": 60,
+            "w": 512,
+            "h": 512,
+            "layers": [
+                {
+                    "ty": 4,
+                    "ind": 1,
+                    "st": 0,
+                    "ip": 0,
+                    "op": 60,
+                    "ks": transform,
+                    "shapes": [
+                        {
+                            "ty": "gr",
+                            "it": [
+                                {
+                                    "ty": "rc",
+                                    "p": { "a": 0, "k": [0, 0] },
+                                    "s": { "a": 0, "k": [10, 10] },
+                                    "r": { "a": 0, "k": 0 }
+                                },
+                                group_transform
+                            ]
+                        }
+                    ]
+                }
+            ]
+        }
+    );
+    let composition = vellottie::runtime::Composition::from_bytes(
+        serde_json::to_vec(&lottie).unwrap(),
+    )
+    .unwrap();
+    let affine = composition.layers[0].transform.evaluate(0.0).to_owned();
+    let origin = affine * Point::ZERO;
+    let x_axis = affine * Point::new(1.0, 0.0) - origin;
+    assert!(origin.distance(Point::new(10.0, 20.0)) < 1e-6);
+    assert!(x_axis.x.abs() < 1e-6);
+    assert!((x_axis.y - 1.0).abs() < 1e-6);
+}
+
 lazy_static! {
     static ref JSON: serde_json::Value = json!(
         {
@@ -0,0 +1,201 @@
+use serde_json::json;
+use vellottie::runtime::{
+    model::fixed,
+    vello::{
+        kurbo::{Affine, Rect, Shape},
+        peniko::{BlendMode, Fill},
+    },
+    Composition, RenderSink, Renderer,
+};
+
+/// Render sink that records the bounds of each stroke.
+#[derive(Default)]
+struct StrokeBounds(Vec<Rect>);
+
+impl RenderSink for StrokeBounds {
+    fn push_layer(
+        &mut self,
+        _blend: impl Into<BlendMode>,
+        _alpha: f32,
+        _transform: Affine,
+        _shape: &impl Shape,
+    ) {
+    }
+
+    fn pop_layer(&mut self) {}
+
+    fn draw(
+        &mut self,
+        stroke: Option<&fixed::Stroke>,
+        _fill: Fill,
+        transform: Affine,
+        _brush: &fixed::Brush,
+        shape: &impl Shape,
+    ) {
+        assert!(stroke.is_some());
+        self.0
+            .push(transform.transform_rect_bbox(shape.bounding_box()));
+    }
+}
+
+fn static_value(value: serde_json::Value) -> serde_json::Value {
+    json!({ "a": 0, "k": value })
+}
+
+/// 10x10 square at the given x offset.
+fn square(x: f64) -> serde_json::Value {
+    json!({
+        "ty": "rc",
+        "p": static_value(json!([x + 5.0, 5])),
+        "s": static_value(json!([10, 10])),
+        "r": static_value(json!(0))
+    })
+}
+
+fn trim(start: f32, end: f32, offset: f32, mode: u8) -> serde_json::Value {
+    json!({
+        "ty": "tm",
+        "s": static_value(json!(start)),
+        "e": static_value(json!(end)),
+        "o": static_value(json!(offset)),
+        "m": mode
+    })
+}
+
+/// Bounds of the strokes of `shapes`, followed by a stroke and `trim`.
+fn trimmed_bounds(
+    shapes: &[serde_json::Value],
+    trim: serde_json::Value,
+) -> Vec<Rect> {
+    let mut shapes = shapes.to_vec();
+    shapes.push(json!({
+        "ty": "st",
+        "c": static_value(json!([0, 0, 1])),
+        "o": static_value(json!(100)),
+        "w": static_value(json!(1)),
+    }));
+    shapes.push(trim);
+    let lottie = json!({
+        "v": "5.5.2",
+        "fr": 10,
+        "ip": 0,
+        "op": 20,
+        "w": 100,
+        "h": 100,
+        "layers": [{
+            "ty": 4,
+            "ind": 1,
+            "st": 0,
+            "ip": 0,
+            "op": 20,
+            "ks": {
+                "a": static_value(json!([0, 0])),
+                "p": static_value(json!([0, 0])),
+                "s": static_value(json!([100, 100])),
+                "r": static_value(json!(0)),
+                "o": static_value(json!(100))
+            },
+            "shapes": shapes
+        }]
+    });
+    let composition =
+        Composition::from_bytes(serde_json::to_vec(&lottie).unwrap()).unwrap();
+    let mut sink = StrokeBounds::default();
+    Renderer::new().render_frame(
+        &composition,
+        0.0,
+        Affine::IDENTITY,
+        1.0,
+        &mut sink,
+    );
+    sink.0
+}
+
+#[test]
+fn test_trim_path() {
+    let bounds = |trim| trimmed_bounds(&[square(0.0)], trim);
+    // The square starts at its top left corner and runs clockwise.
+    assert_eq!(
+        bounds(trim(0.0, 100.0, 0.0, 1)),
+        [Rect::new(0.0, 0.0, 10.0, 10.0)]
+    );
+    assert_eq!(
+        bounds(trim(0.0, 25.0, 0.0, 1)),
+        [Rect::new(0.0, 0.0, 10.0, 0.0)]
+    );
+    // Start and end are ordered.
+    assert_eq!(
+        bounds(trim(50.0, 25.0, 0.0, 1)),
+        [Rect::new(10.0, 0.0, 10.0, 10.0)]
+    );
+    // Offset of a quarter turn moves the segment to the next edge.
+    assert_eq!(
+        bounds(trim(0.0, 25.0, 90.0, 1)),
+        [Rect::new(10.0, 0.0, 10.0, 10.0)]
+    );
+    // Wrapping around the start of the path.
+    assert_eq!(
+        bounds(trim(0.0, 50.0, -90.0, 1)),
+        [Rect::new(0.0, 0.0, 10.0, 10.0)]
+    );
+    let [wrapped] = bounds(trim(0.0, 25.0, -45.0, 1))[..] else {
+        panic!("expected a single draw");
+    };
+    assert_eq!(wrapped.origin(), (0.0, 0.0).into());
+    assert!((wrapped.x1 - 5.0).abs() < 1e-6);
+    assert!((wrapped.y1 - 5.0).abs() < 1e-6);
+    // Empty segment.
+    assert!(bounds(trim(30.0, 30.0, 0.0, 1)).is_empty());
+}
+
+#[test]
+fn test_trim_curve() {
+    // Curves are measured by arc length, so half of a circle starting at
+    // its rightmost point is the bottom half.
+    let circle = json!({
+        "ty": "el",
+        "p": static_value(json!([5, 5])),
+        "s": static_value(json!([10, 10]))
+    });
+    let [half] = trimmed_bounds(&[circle], trim(0.0, 50.0, 0.0, 1))[..] else {
+        panic!("expected a single draw");
+    };
+    assert!((half.width() - 10.0).abs() < 1e-3, "{half:?}");
+    assert!((half.height() - 5.0).abs() < 1e-3, "{half:?}");
+}
+
+#[test]
+fn test_trim_multiple_shapes() {
+    let bounds = |trim| trimmed_bounds(&[square(0.0), square(20.0)], trim);
+    // Simultaneously trims each square to its top edge.
+    assert_eq!(
+        bounds(trim(0.0, 25.0, 0.0, 1)),
+        [
+            Rect::new(0.0, 0.0, 10.0, 0.0),
+            Rect::new(20.0, 0.0, 30.0, 0.0)
+        ]
+    );
+    // Individually trims the squares as one path, covering only the first.
+    assert_eq!(
+        bounds(trim(0.0, 50.0, 0.0, 2)),
+        [Rect::new(0.0, 0.0, 10.0, 10.0)]
+    );
+    assert_eq!(
+        bounds(trim(50.0, 62.5, 0.0, 2)),
+        [Rect::new(20.0, 0.0, 30.0, 0.0)]
+    );
+}
+
+#[test]
+fn test_trim_subpaths() {
+    // Merging the squares makes a single shape with two subpaths, which is
+    // trimmed as one path even when trimming simultaneously.
+    let merge = json!({ "ty": "mm", "mm": 2 });
+    let [half] = trimmed_bounds(
+        &[square(0.0), square(20.0), merge],
+        trim(0.0, 50.0, 0.0, 1),
+    )[..] else {
+        panic!("expected a single draw");
+    };
+    assert_eq!(half.size(), (10.0, 10.0).into());
+}